@@ -0,0 +1,37 @@
+extern crate rsrl;
+
+use rand::thread_rng;
+use rsrl::{
+    control::bandits::{BetaThompson, EpsilonGreedy, KLUCB, UCB1},
+    domains::{
+        bandits::{Bandit, Bernoulli, Regret},
+        Domain,
+        Transition,
+    },
+    policies::Policy,
+    Handler,
+};
+
+fn run<P>(name: &str, mut agent: P)
+where P: for<'s> Policy<&'s usize, Action = usize>
+        + for<'m> Handler<&'m Transition<usize, usize>> {
+    let mut rng = thread_rng();
+    let mut env = Bernoulli::new(vec![0.1, 0.3, 0.5, 0.55, 0.6]);
+    let mut regret = Regret::new();
+
+    for _ in 0..10000 {
+        let a = agent.sample(&mut rng, env.emit().state());
+
+        regret.record(&env.expected_rewards(), a);
+        agent.handle(&env.transition(a)).ok();
+    }
+
+    println!("{}: {:.2}", name, regret.regret());
+}
+
+fn main() {
+    run("ε-greedy", EpsilonGreedy::new(5, 0.1));
+    run("UCB1", UCB1::new(5));
+    run("KL-UCB", KLUCB::new(5));
+    run("Thompson", BetaThompson::new(5));
+}
@@ -0,0 +1,39 @@
+extern crate rsrl;
+
+use rsrl::{
+    control::bbo::{flat_weights, PolicySearch, CEM},
+    domains::{CartPole, Domain},
+    fa::linear::{
+        basis::{Combinators, Polynomial},
+        optim::SGD,
+        LFA,
+    },
+    policies::{Gibbs, Policy},
+    spaces::Space,
+    Handler,
+};
+
+fn main() {
+    let domain = CartPole::default();
+    let n_actions = domain.action_space().card().into();
+
+    let basis = Polynomial::new(domain.state_space().dim().into(), 1).with_bias();
+    let policy = Gibbs::standard(LFA::vector(basis, SGD(1.0), n_actions));
+
+    let strategy = CEM::new(flat_weights(&policy), 1.0, 50, 10).with_noise(0.01);
+    let mut agent = PolicySearch::new(policy, strategy)
+        .with_episodes(5)
+        .with_step_limit(1000)
+        .with_threads(4);
+
+    for e in 0..50 {
+        let response = agent.handle(CartPole::default).unwrap();
+        let best = response.fitness.iter().fold(f64::MIN, |acc, &f| acc.max(f));
+
+        println!("Generation {}: {}", e + 1, best);
+    }
+
+    let traj = CartPole::default().rollout(|s| agent.policy.mode(s), Some(1000));
+
+    println!("OOS: {}...", traj.total_reward());
+}
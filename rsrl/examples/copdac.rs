@@ -0,0 +1,41 @@
+extern crate rsrl;
+
+use rand::thread_rng;
+use rsrl::{
+    control::dpg::COPDACGQ,
+    domains::{ContinuousMountainCar, Domain},
+    fa::linear::{
+        basis::{Combinators, Fourier},
+        optim::SGD,
+        LFA,
+    },
+    policies::{Noisy, Point, Policy},
+    Handler,
+};
+
+fn main() {
+    let domain = ContinuousMountainCar::default();
+    let basis = Fourier::from_space(3, domain.state_space()).with_bias();
+
+    let critic = LFA::scalar(basis.clone(), SGD(1.0));
+    let policy = Point::new(LFA::scalar(basis, SGD(1.0)));
+
+    let mut rng = thread_rng();
+    let mut agent = COPDACGQ::new(critic, policy, 0.0001, 0.001, 0.01, 0.99);
+
+    for e in 0..100 {
+        let behaviour = Noisy::new(agent.policy.clone(), 1.0);
+        let traj = ContinuousMountainCar::default()
+            .rollout(|s| behaviour.sample(&mut rng, s), Some(1000));
+
+        println!("Batch {}: {}", e + 1, traj.total_reward());
+
+        for t in traj.into_batch().iter() {
+            agent.handle(t).ok();
+        }
+    }
+
+    let traj = ContinuousMountainCar::default().rollout(|s| agent.policy.mode(s), Some(1000));
+
+    println!("OOS: {}...", traj.total_reward());
+}
@@ -0,0 +1,41 @@
+extern crate rsrl;
+
+use rand::thread_rng;
+use rsrl::{
+    control::nac::ENAC,
+    domains::{ContinuousMountainCar, Domain},
+    fa::linear::{
+        basis::{Combinators, Fourier},
+        optim::SGD,
+        LFA,
+    },
+    policies::{Gaussian, Policy},
+    Handler,
+};
+
+fn main() {
+    let domain = ContinuousMountainCar::default();
+    let basis = Fourier::from_space(3, domain.state_space()).with_bias();
+    let policy = Gaussian::new(LFA::scalar(basis, SGD(1.0)), 1.0);
+
+    let mut rng = thread_rng();
+    let mut agent = ENAC::new(policy, 0.01, 0.99);
+
+    for e in 0..100 {
+        let trajs: Vec<_> = (0..10)
+            .map(|_| {
+                ContinuousMountainCar::default()
+                    .rollout(|s| agent.policy.sample(&mut rng, s), Some(1000))
+            })
+            .collect();
+        let mean_reward = trajs.iter().map(|t| t.total_reward()).sum::<f64>() / 10.0;
+
+        agent.handle(&trajs).ok();
+
+        println!("Batch {}: {}", e + 1, mean_reward);
+    }
+
+    let traj = ContinuousMountainCar::default().rollout(|s| agent.policy.mode(s), Some(1000));
+
+    println!("OOS: {}...", traj.total_reward());
+}
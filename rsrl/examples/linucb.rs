@@ -0,0 +1,46 @@
+extern crate rsrl;
+
+use rand::thread_rng;
+use rsrl::{
+    control::bandits::{LinUCB, LinearThompson},
+    domains::{
+        bandits::{ContextualBandit, LinearContextual, Regret},
+        Domain,
+        Transition,
+    },
+    fa::linear::basis::{Combinators, Polynomial},
+    policies::Policy,
+    Handler,
+};
+
+fn run<P>(name: &str, mut agent: P)
+where P: for<'s> Policy<&'s Vec<f64>, Action = usize>
+        + for<'m> Handler<&'m Transition<Vec<f64>, usize>> {
+    let mut rng = thread_rng();
+    let mut env = LinearContextual::new(
+        vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+            vec![-0.5, -0.5, -0.5],
+        ],
+        0.1,
+    );
+    let mut regret = Regret::new();
+
+    for _ in 0..10000 {
+        let a = agent.sample(&mut rng, env.emit().state());
+
+        regret.record(&env.expected_rewards(), a);
+        agent.handle(&env.transition(a)).ok();
+    }
+
+    println!("{}: {:.2}", name, regret.dynamic_regret());
+}
+
+fn main() {
+    let basis = Polynomial::new(3, 1).with_bias();
+
+    run("LinUCB", LinUCB::new(basis.clone(), 4, 1.0));
+    run("Thompson", LinearThompson::new(basis, 4, 0.5));
+}
@@ -42,11 +42,7 @@ fn main() {
         };
         let cfa = LFA::scalar(basis_c, optimiser);
 
-        SARSA {
-            q_func: cfa,
-            policy: policy.clone(),
-            gamma: 0.999,
-        }
+        SARSA::new(cfa, policy.clone(), 0.999)
     };
 
     let mut rng = thread_rng();
@@ -0,0 +1,52 @@
+extern crate rsrl;
+
+use rand::thread_rng;
+use rsrl::{
+    control::offpac::OffPAC,
+    domains::{Domain, MountainCar},
+    fa::linear::{
+        basis::{Combinators, Fourier},
+        optim::SGD,
+        LFA,
+    },
+    policies::{Gibbs, Policy, Random},
+    spaces::Space,
+    Handler,
+};
+
+fn main() {
+    let domain = MountainCar::default();
+    let n_actions = domain.action_space().card().into();
+
+    let basis = Fourier::from_space(3, domain.state_space()).with_bias();
+
+    let critic = LFA::scalar(basis.clone(), SGD(1.0));
+    let policy = Gibbs::standard(LFA::vector(basis, SGD(1.0), n_actions));
+    let behaviour = Random::new(n_actions);
+
+    let mut rng = thread_rng();
+    let mut agent = OffPAC::new(critic, policy, behaviour, 0.001, 0.01, 0.99, 0.4);
+
+    for e in 0..1000 {
+        // Episode loop:
+        let mut env = MountainCar::default();
+
+        agent.reset_traces();
+
+        for _ in 0..1000 {
+            // Trajectory loop:
+            let action = agent.behaviour.sample(&mut rng, env.emit().state());
+            let t = env.transition(action);
+
+            agent.handle(&t).ok();
+
+            if t.terminated() {
+                break;
+            }
+        }
+
+        let traj = MountainCar::default().rollout(|s| agent.policy.mode(s), Some(1000));
+
+        println!("Batch {}: {}", e + 1, traj.total_reward());
+    }
+}
@@ -0,0 +1,35 @@
+extern crate rsrl;
+
+use rsrl::{
+    control::bbo::{flat_weights, PolicySearch, PGPE},
+    domains::{ContinuousMountainCar, Domain},
+    fa::linear::{
+        basis::{Combinators, Fourier},
+        optim::SGD,
+        LFA,
+    },
+    policies::{Point, Policy},
+    Handler,
+};
+
+fn main() {
+    let domain = ContinuousMountainCar::default();
+    let basis = Fourier::from_space(3, domain.state_space()).with_bias();
+    let policy = Point::new(LFA::scalar(basis, SGD(1.0)));
+
+    let strategy = PGPE::new(flat_weights(&policy), 1.0, 0.001, 0.0005, 10);
+    let mut agent = PolicySearch::new(policy, strategy)
+        .with_step_limit(1000)
+        .with_threads(4);
+
+    for e in 0..200 {
+        let response = agent.handle(ContinuousMountainCar::default).unwrap();
+        let mean = response.fitness.iter().sum::<f64>() / response.fitness.len() as f64;
+
+        println!("Generation {}: {}", e + 1, mean);
+    }
+
+    let traj = ContinuousMountainCar::default().rollout(|s| agent.policy.mode(s), Some(1000));
+
+    println!("OOS: {}...", traj.total_reward());
+}
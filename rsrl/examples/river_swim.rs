@@ -0,0 +1,59 @@
+extern crate rsrl;
+
+use ndarray::Array2;
+use rand::thread_rng;
+use rsrl::{
+    control::td::{CountBonus, QLearning},
+    counts::TabularCounts,
+    domains::{Domain, RiverSwim},
+    fa::tabular::Table,
+    make_shared,
+    policies::{EpsilonGreedy, Greedy, Policy, Random, UCB},
+    Handler,
+};
+
+const N_STATES: usize = 6;
+const N_STEPS: usize = 20000;
+
+fn main() {
+    let mut rng = thread_rng();
+
+    // Dithering exploration with ε-greedy:
+    let q_func = make_shared(Table::dense(Array2::zeros((N_STATES, 2))));
+    let policy = EpsilonGreedy::new(Greedy::new(q_func.clone()), Random::new(2), 0.1);
+
+    let mut agent = QLearning::new(q_func, 0.95);
+    let mut env = RiverSwim::new(N_STATES);
+    let mut total = 0.0;
+
+    for _ in 0..N_STEPS {
+        let t = env.transition(policy.sample(&mut rng, env.emit().state()));
+
+        total += t.reward;
+        agent.handle(&t).ok();
+    }
+
+    println!("ε-greedy: {:.2}", total);
+
+    // Directed exploration with optimism, UCB and a count-based bonus:
+    let q_func = make_shared(Table::optimistic((N_STATES, 2), 20.0));
+    let mut policy = UCB::new(q_func.clone(), TabularCounts::new(N_STATES, 2), 1.0);
+
+    let mut agent = CountBonus::new(
+        QLearning::new(q_func, 0.95),
+        TabularCounts::new(N_STATES, 2),
+        0.1,
+    );
+    let mut env = RiverSwim::new(N_STATES);
+    let mut total = 0.0;
+
+    for _ in 0..N_STEPS {
+        let t = env.transition(policy.sample(&mut rng, env.emit().state()));
+
+        total += t.reward;
+        policy.handle(&t).ok();
+        agent.handle(&t).ok();
+    }
+
+    println!("UCB + bonus: {:.2}", total);
+}
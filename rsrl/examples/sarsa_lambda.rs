@@ -12,6 +12,7 @@ use rsrl::{
     make_shared,
     params::Parameterised,
     policies::{EpsilonGreedy, Greedy, Policy, Random},
+    schedules::{Exponential, Schedule},
     spaces::Space,
     traces::Trace,
     Handler,
@@ -31,7 +32,9 @@ fn main() {
         let basis = Fourier::from_space(5, env.state_space()).with_bias();
         let fa_theta = make_shared(LFA::vector(basis, SGD(1.0), n_actions));
 
-        let policy = EpsilonGreedy::new(Greedy::new(fa_theta.clone()), Random::new(n_actions), 0.2);
+        let epsilon = Exponential::new(0.2, 0.995).per_episode();
+        let policy =
+            EpsilonGreedy::new(Greedy::new(fa_theta.clone()), Random::new(n_actions), epsilon);
         let wdim = fa_theta.weights_dim();
 
         let trace = Trace::replacing(wdim, GAMMA, LAMBDA);
@@ -65,7 +68,7 @@ fn main() {
             }
         }
 
-        agent.policy.epsilon *= 0.995;
+        agent.policy.epsilon.end_episode();
 
         println!("Batch {}: {} steps...", e + 1, j + 1);
     }
@@ -11,6 +11,7 @@ use rsrl::{
         LFA,
     },
     make_shared,
+    optim::{Adam, ClipNorm, Optimised},
     policies::{Gaussian, Policy},
     prediction::lstd::iLSTD,
     spaces::Space,
@@ -23,7 +24,7 @@ fn main() {
 
     let lfa = LFA::scalar(basis.clone(), SGD(1.0));
 
-    let policy = Gaussian::new(lfa, 1.0);
+    let policy = Optimised::new(Gaussian::new(lfa, 1.0), ClipNorm::new(Adam::new(0.002), 10.0));
     let mut eval = shared!(iLSTD::new(basis, 0.0001, 0.99, 2));
 
     let critic = {
@@ -33,7 +34,7 @@ fn main() {
     };
 
     let mut rng = thread_rng();
-    let mut agent = ActorCritic::tdac(critic, policy, 0.002, 0.99);
+    let mut agent = ActorCritic::tdac(critic, policy, 1.0, 0.99);
 
     for e in 0..100 {
         // Episode loop:
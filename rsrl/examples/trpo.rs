@@ -0,0 +1,38 @@
+extern crate rsrl;
+
+use rand::thread_rng;
+use rsrl::{
+    control::trpo::TRPO,
+    domains::{ContinuousMountainCar, Domain},
+    fa::linear::{
+        basis::{Combinators, Fourier},
+        optim::SGD,
+        LFA,
+    },
+    policies::{Gaussian, Policy},
+    Handler,
+};
+
+fn main() {
+    let domain = ContinuousMountainCar::default();
+    let basis = Fourier::from_space(3, domain.state_space()).with_bias();
+
+    let v_func = LFA::scalar(basis.clone(), SGD(0.001));
+    let policy = Gaussian::new(LFA::scalar(basis, SGD(1.0)), 1.0);
+
+    let mut rng = thread_rng();
+    let mut agent = TRPO::new(v_func, policy, 0.99, 0.95, 0.01);
+
+    for e in 0..100 {
+        let traj = ContinuousMountainCar::default()
+            .rollout(|s| agent.policy.sample(&mut rng, s), Some(1000));
+
+        agent.handle(&traj).ok();
+
+        println!("Batch {}: {}", e + 1, traj.total_reward());
+    }
+
+    let traj = ContinuousMountainCar::default().rollout(|s| agent.policy.mode(s), Some(1000));
+
+    println!("OOS: {}...", traj.total_reward());
+}
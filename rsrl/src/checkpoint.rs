@@ -0,0 +1,142 @@
+//! Agent checkpointing module.
+//!
+//! Helpers for saving and restoring any serialisable agent, policy or
+//! function approximator. Sharing between `Shared` instances (e.g. a
+//! Q-function held by both an agent and its `Greedy` policy) is preserved
+//! across a round-trip; see `preserve_sharing`.
+use crate::preserve_sharing;
+use serde_crate::{de::DeserializeOwned, Serialize};
+use std::{
+    error,
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// Encoding used for a checkpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable JSON.
+    Json,
+
+    /// Compact binary encoding (`bincode`).
+    Binary,
+}
+
+/// Error type for saving and loading checkpoints.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Binary(bincode::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(ref e) => write!(f, "I/O error: {}", e),
+            Error::Json(ref e) => write!(f, "JSON error: {}", e),
+            Error::Binary(ref e) => write!(f, "binary encoding error: {}", e),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(ref e) => Some(e),
+            Error::Json(ref e) => Some(e),
+            Error::Binary(ref e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error { Error::Io(e) }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Error { Error::Json(e) }
+}
+
+impl From<bincode::Error> for Error {
+    fn from(e: bincode::Error) -> Error { Error::Binary(e) }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Serialise `value` to `writer`.
+pub fn to_writer<W: Write, T: Serialize>(writer: W, format: Format, value: &T) -> Result<()> {
+    preserve_sharing(|| match format {
+        Format::Json => serde_json::to_writer(writer, value).map_err(Error::from),
+        Format::Binary => bincode::serialize_into(writer, value).map_err(Error::from),
+    })
+}
+
+/// Deserialise a value from `reader`.
+pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R, format: Format) -> Result<T> {
+    preserve_sharing(|| match format {
+        Format::Json => serde_json::from_reader(reader).map_err(Error::from),
+        Format::Binary => bincode::deserialize_from(reader).map_err(Error::from),
+    })
+}
+
+/// Save a checkpoint of `value` to the file at `path`.
+pub fn save<P: AsRef<Path>, T: Serialize>(path: P, format: Format, value: &T) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    to_writer(&mut writer, format, value)?;
+
+    writer.flush().map_err(Error::from)
+}
+
+/// Load a checkpoint from the file at `path`.
+pub fn load<P: AsRef<Path>, T: DeserializeOwned>(path: P, format: Format) -> Result<T> {
+    from_reader(BufReader::new(File::open(path)?), format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        control::td::QLearning,
+        fa::tabular::Table,
+        make_shared,
+        policies::Greedy,
+        Shared,
+    };
+    use ndarray::Array2;
+
+    type Q = Shared<Table<Array2<f64>>>;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(crate = "serde_crate")]
+    struct Agent {
+        learner: QLearning<Q>,
+        policy: Greedy<Q>,
+    }
+
+    fn round_trip(format: Format) {
+        let q_func = make_shared(Table::dense(Array2::zeros((2, 2))));
+        let agent = Agent {
+            learner: QLearning::new(q_func.clone(), 0.9),
+            policy: Greedy::new(q_func),
+        };
+
+        let mut bytes = vec![];
+
+        to_writer(&mut bytes, format, &agent).unwrap();
+
+        let agent: Agent = from_reader(bytes.as_slice(), format).unwrap();
+
+        assert_eq!(agent.learner.gamma, 0.9);
+        assert_eq!(std::rc::Rc::strong_count(&agent.learner.q_func.0), 2);
+    }
+
+    #[test]
+    fn test_json_round_trip() { round_trip(Format::Json); }
+
+    #[test]
+    fn test_binary_round_trip() { round_trip(Format::Binary); }
+}
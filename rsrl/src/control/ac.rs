@@ -1,8 +1,9 @@
 //! Actor-critic algorithms.
 use crate::{
-    domains::Transition,
-    fa::StateActionUpdate,
+    domains::{Batch, Trajectory, Transition},
+    fa::{StateActionUpdate, StateUpdate},
     policies::Policy,
+    schedules::Schedule,
     Function,
     Handler,
 };
@@ -19,6 +20,12 @@ where F: Fn(&'t Transition<S, A>) -> f64,
     }
 }
 
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct QCritic<Q>(pub Q);
 
 impl<'t, Q, S: 't, A: 't> Critic<'t, S, A> for QCritic<Q>
@@ -29,6 +36,12 @@ where Q: Function<(&'t S, &'t A), Output = f64>,
     }
 }
 
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct TDCritic<V> {
     pub gamma: f64,
     pub v_func: V,
@@ -56,36 +69,43 @@ where V: Function<(&'t S,), Output = f64>,
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate")
 )]
-pub struct ActorCritic<C, P> {
+pub struct ActorCritic<C, P, A = f64> {
     pub critic: C,
     pub policy: P,
 
-    pub alpha: f64,
+    pub alpha: A,
+    pub entropy: f64,
 }
 
-impl<C, P> ActorCritic<C, P> {
-    pub fn new(critic: C, policy: P, alpha: f64) -> Self {
+impl<C, P, A> ActorCritic<C, P, A> {
+    pub fn new(critic: C, policy: P, alpha: A) -> Self {
         ActorCritic {
             critic,
             policy,
 
             alpha,
+            entropy: 0.0,
         }
     }
+
+    /// Add a sampled entropy bonus, `-entropy * ln π(a | s)`, to the error
+    /// used to update the actor; the critic is unaffected.
+    pub fn with_entropy(self, entropy: f64) -> Self { ActorCritic { entropy, ..self } }
 }
 
-impl<Q, P> ActorCritic<QCritic<Q>, P> {
-    pub fn qac(q_func: Q, policy: P, alpha: f64) -> Self {
+impl<Q, P, A> ActorCritic<QCritic<Q>, P, A> {
+    pub fn qac(q_func: Q, policy: P, alpha: A) -> Self {
         ActorCritic {
             critic: QCritic(q_func),
             policy,
             alpha,
+            entropy: 0.0,
         }
     }
 }
 
-impl<V, P> ActorCritic<TDCritic<V>, P> {
-    pub fn tdac(v_func: V, policy: P, alpha: f64, gamma: f64) -> Self {
+impl<V, P, A> ActorCritic<TDCritic<V>, P, A> {
+    pub fn tdac(v_func: V, policy: P, alpha: A, gamma: f64) -> Self {
         ActorCritic {
             critic: TDCritic {
                 gamma,
@@ -93,23 +113,283 @@ impl<V, P> ActorCritic<TDCritic<V>, P> {
             },
             policy,
             alpha,
+            entropy: 0.0,
         }
     }
 }
 
-impl<'m, S, C, P> Handler<&'m Transition<S, P::Action>> for ActorCritic<C, P>
+impl<'m, S, C, P, A> Handler<&'m Transition<S, P::Action>> for ActorCritic<C, P, A>
 where
     C: Critic<'m, S, P::Action>,
+    A: Schedule,
     P: Policy<&'m S> + Handler<StateActionUpdate<&'m S, &'m <P as Policy<&'m S>>::Action, f64>>,
 {
     type Response = P::Response;
     type Error = P::Error;
 
     fn handle(&mut self, t: &'m Transition<S, P::Action>) -> Result<Self::Response, Self::Error> {
-        self.policy.handle(StateActionUpdate {
-            state: t.from.state(),
+        let s = t.from.state();
+        let bonus = if self.entropy > 0.0 {
+            -self.entropy * self.policy.evaluate((s, &t.action)).ln()
+        } else {
+            0.0
+        };
+
+        let response = self.policy.handle(StateActionUpdate {
+            state: s,
             action: &t.action,
-            error: self.alpha * self.critic.target(&t),
-        })
+            error: self.alpha.value() * (self.critic.target(&t) + bonus),
+        });
+
+        self.alpha.step();
+
+        if t.terminated() {
+            self.alpha.end_episode();
+        }
+
+        response
+    }
+}
+
+/// Compute generalised advantage estimates, GAE(γ, λ), for a contiguous
+/// sequence of transitions.
+///
+/// Advantages are propagated backwards through the sequence and reset at each
+/// terminal transition.
+///
+/// # References
+/// - Schulman, J., Moritz, P., Levine, S., Jordan, M., Abbeel, P. (2016).
+/// High-dimensional continuous control using generalized advantage estimation.
+/// In Proceedings of ICLR.
+pub fn gae<'m, S: 'm, A: 'm, V>(
+    v_func: &V,
+    transitions: &[Transition<&'m S, &'m A>],
+    gamma: f64,
+    lambda: f64,
+) -> Vec<f64>
+where
+    V: Function<(&'m S,), Output = f64>,
+{
+    let mut advantages = vec![0.0; transitions.len()];
+    let mut acc = 0.0;
+
+    for (i, t) in transitions.iter().enumerate().rev() {
+        let v = v_func.evaluate((*t.from.state(),));
+
+        acc = if t.terminated() {
+            t.reward - v
+        } else {
+            let nv = v_func.evaluate((*t.to.state(),));
+
+            t.reward + gamma * nv - v + gamma * lambda * acc
+        };
+
+        advantages[i] = acc;
+    }
+
+    advantages
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum Error<EV, EP> {
+    CriticError(EV),
+    ActorError(EP),
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Response {
+    /// Generalised advantage estimates for each transition in the batch.
+    pub advantages: Vec<f64>,
+}
+
+/// Batch advantage actor-critic (A2C) with generalised advantage estimation.
+///
+/// Each batch is treated as a contiguous sequence of transitions, possibly
+/// spanning several episodes; advantages are propagated backwards until a
+/// terminal transition is reached. All advantages are computed against the
+/// value function before any updates are applied (see `gae`). The critic is
+/// then regressed onto the λ-returns, and the actor is updated with the
+/// advantages, optionally regularised by a sampled entropy bonus,
+/// `-entropy * ln π(a | s)`.
+///
+/// # References
+/// - Mnih, V., et al. (2016). Asynchronous methods for deep reinforcement
+/// learning. In Proceedings of ICML, pp. 1928-1937.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct A2C<V, P, A = f64> {
+    pub v_func: V,
+    pub policy: P,
+
+    pub alpha: A,
+    pub gamma: f64,
+    pub lambda: f64,
+    pub entropy: f64,
+}
+
+impl<V, P, A> A2C<V, P, A> {
+    pub fn new(v_func: V, policy: P, alpha: A, gamma: f64, lambda: f64) -> Self {
+        A2C {
+            v_func,
+            policy,
+
+            alpha,
+            gamma,
+            lambda,
+            entropy: 0.0,
+        }
+    }
+
+    /// Add an entropy regularisation term with weight `entropy`.
+    pub fn with_entropy(self, entropy: f64) -> Self { A2C { entropy, ..self } }
+}
+
+impl<V, P, A: Schedule> A2C<V, P, A> {
+    fn update<'m, S>(
+        &mut self,
+        transitions: &[Transition<&'m S, &'m P::Action>],
+    ) -> Result<Response, Error<V::Error, P::Error>>
+    where
+        V: Function<(&'m S,), Output = f64> + Handler<StateUpdate<&'m S, f64>>,
+        P: Policy<&'m S> + Handler<StateActionUpdate<&'m S, &'m <P as Policy<&'m S>>::Action>>,
+    {
+        let advantages = gae(&self.v_func, transitions, self.gamma, self.lambda);
+
+        for (t, &adv) in transitions.iter().zip(advantages.iter()) {
+            let s = *t.from.state();
+            let bonus = if self.entropy > 0.0 {
+                -self.entropy * self.policy.evaluate((s, t.action)).ln()
+            } else {
+                0.0
+            };
+
+            self.v_func
+                .handle(StateUpdate {
+                    state: s,
+                    error: adv,
+                })
+                .map_err(Error::CriticError)?;
+
+            self.policy
+                .handle(StateActionUpdate {
+                    state: s,
+                    action: t.action,
+                    error: self.alpha.value() * (adv + bonus),
+                })
+                .map_err(Error::ActorError)?;
+
+            self.alpha.step();
+
+            if t.terminated() {
+                self.alpha.end_episode();
+            }
+        }
+
+        Ok(Response { advantages })
+    }
+}
+
+impl<'m, S, V, P, A> Handler<&'m Batch<S, P::Action>> for A2C<V, P, A>
+where
+    V: Function<(&'m S,), Output = f64> + Handler<StateUpdate<&'m S, f64>>,
+    P: Policy<&'m S> + Handler<StateActionUpdate<&'m S, &'m <P as Policy<&'m S>>::Action>>,
+    A: Schedule,
+{
+    type Response = Response;
+    type Error = Error<V::Error, P::Error>;
+
+    fn handle(&mut self, batch: &'m Batch<S, P::Action>) -> Result<Response, Self::Error> {
+        let transitions: Vec<_> = batch.iter().map(|t| t.borrowed()).collect();
+
+        self.update(&transitions)
+    }
+}
+
+impl<'m, S, V, P, A> Handler<&'m Trajectory<S, P::Action>> for A2C<V, P, A>
+where
+    V: Function<(&'m S,), Output = f64> + Handler<StateUpdate<&'m S, f64>>,
+    P: Policy<&'m S> + Handler<StateActionUpdate<&'m S, &'m <P as Policy<&'m S>>::Action>>,
+    A: Schedule,
+{
+    type Response = Response;
+    type Error = Error<V::Error, P::Error>;
+
+    fn handle(&mut self, traj: &'m Trajectory<S, P::Action>) -> Result<Response, Self::Error> {
+        let transitions: Vec<Transition<&'m S, &'m P::Action>> = traj.iter().collect();
+
+        self.update(&transitions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::Observation,
+        fa::tabular::Table,
+        params::Parameterised,
+        policies::Softmax,
+    };
+    use ndarray::{Array1, Array2};
+
+    #[test]
+    fn test_actor_critic_softmax_entropy() {
+        let policy = Softmax::standard(Table::dense(Array2::zeros((2, 3))));
+        let mut agent =
+            ActorCritic::tdac(Table::dense(Array1::zeros(2)), policy, 0.1, 0.9).with_entropy(0.01);
+
+        let t = Transition {
+            from: Observation::Full(0usize),
+            action: 2usize,
+            reward: 1.0,
+            to: Observation::Full(1),
+        };
+
+        assert!(agent.handle(&t).is_ok());
+
+        // π(2 | 0) = 1/3, so the bonus is 0.01 ln 3 and ∇ ln π = e_2 - 1/3.
+        let w = agent.policy.weights_view();
+        let step = 0.1 * (1.0 + 0.01 * 3.0f64.ln());
+
+        assert!((w[(0, 2)] - step * 2.0 / 3.0).abs() < 1e-10);
+        assert!((w[(0, 0)] + step / 3.0).abs() < 1e-10);
+        assert!(w.iter().all(|w| w.is_finite()));
+    }
+
+    #[test]
+    fn test_a2c_softmax_entropy() {
+        let v_func = Table::dense(Array1::zeros(2));
+        let policy = Softmax::standard(Table::dense(Array2::zeros((2, 3))));
+        let mut agent = A2C::new(v_func, policy, 0.1, 0.9, 0.95).with_entropy(0.01);
+
+        let traj = Trajectory {
+            start: Observation::Full(0usize),
+            steps: vec![
+                (Observation::Full(1), 2usize, 1.0),
+                (Observation::Terminal(0), 0, -1.0),
+            ],
+        };
+
+        assert!(agent.handle(&traj).is_ok());
+        assert!(agent.policy.weights_view().iter().all(|w| w.is_finite()));
+
+        let ps = agent.policy.evaluate((&0,));
+
+        assert!((ps.iter().sum::<f64>() - 1.0).abs() < 1e-10);
+        assert!(ps[2] > ps[1]);
     }
 }
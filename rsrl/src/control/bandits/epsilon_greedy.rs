@@ -0,0 +1,75 @@
+use super::{greedy_prob, ArmStatistics};
+use crate::{
+    domains::Transition,
+    policies::Policy,
+    schedules::Schedule,
+    utils::{argmax_choose_rng, argmax_first},
+    Function,
+    Handler,
+};
+use rand::Rng;
+
+/// ε-greedy bandit agent acting on the empirical mean reward of each arm.
+///
+/// The exploration rate `epsilon` may be any `Schedule`, which is stepped once
+/// per pull.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct EpsilonGreedy<E = f64> {
+    pub stats: ArmStatistics,
+    pub epsilon: E,
+}
+
+impl<E> EpsilonGreedy<E> {
+    pub fn new(n_arms: usize, epsilon: E) -> Self {
+        EpsilonGreedy {
+            stats: ArmStatistics::new(n_arms),
+            epsilon,
+        }
+    }
+}
+
+impl<S, A, E> Function<(S, A)> for EpsilonGreedy<E>
+where
+    A: std::borrow::Borrow<usize>,
+    E: Schedule,
+{
+    type Output = f64;
+
+    fn evaluate(&self, (_, a): (S, A)) -> f64 {
+        let epsilon = self.epsilon.value();
+        let greedy = greedy_prob(self.stats.means.clone(), *a.borrow());
+
+        epsilon / self.stats.n_arms() as f64 + (1.0 - epsilon) * greedy
+    }
+}
+
+impl<S, E: Schedule> Policy<S> for EpsilonGreedy<E> {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, _: S) -> usize {
+        if rng.gen_bool(self.epsilon.value()) {
+            rng.gen_range(0, self.stats.n_arms())
+        } else {
+            argmax_choose_rng(rng, self.stats.means.iter().cloned()).0
+        }
+    }
+
+    fn mode(&self, _: S) -> usize { argmax_first(self.stats.means.iter().cloned()).0 }
+}
+
+impl<'m, S, E: Schedule> Handler<&'m Transition<S, usize>> for EpsilonGreedy<E> {
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<(), ()> {
+        self.stats.update(t.action, t.reward);
+        self.epsilon.step();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{
+    domains::Transition,
+    policies::{sample_probs_with_rng, Policy},
+    utils::argmax_first,
+    Function,
+    Handler,
+};
+use rand::Rng;
+
+/// Exponential-weight algorithm for exploration and exploitation (EXP3).
+///
+/// Arms are drawn from a mixture of an exponential-weights distribution and
+/// the uniform distribution, with mixing coefficient `gamma`. The pulled arm's
+/// weight is then updated with an importance-weighted estimate of its reward,
+/// which must lie in `[0, 1]`. Unlike the stochastic bandit agents, EXP3 makes
+/// no assumptions on how rewards are generated and so is suited to
+/// adversarial bandits.
+///
+/// # References
+/// - Auer, P., Cesa-Bianchi, N., Freund, Y., Schapire, R. E. (2002). The
+/// nonstochastic multiarmed bandit problem. SIAM Journal on Computing, 32(1),
+/// 48-77.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct EXP3 {
+    pub gamma: f64,
+
+    log_weights: Vec<f64>,
+}
+
+impl EXP3 {
+    pub fn new(n_arms: usize, gamma: f64) -> Self {
+        EXP3 {
+            gamma,
+
+            log_weights: vec![0.0; n_arms],
+        }
+    }
+
+    /// Return the probability of pulling each arm.
+    pub fn probabilities(&self) -> Vec<f64> {
+        let k = self.log_weights.len() as f64;
+        let max_lw = self.log_weights.iter().fold(f64::MIN, |acc, &w| acc.max(w));
+
+        let ws: Vec<f64> = self.log_weights.iter().map(|lw| (lw - max_lw).exp()).collect();
+        let z: f64 = ws.iter().sum();
+
+        ws.into_iter()
+            .map(|w| (1.0 - self.gamma) * w / z + self.gamma / k)
+            .collect()
+    }
+}
+
+impl<S, A: std::borrow::Borrow<usize>> Function<(S, A)> for EXP3 {
+    type Output = f64;
+
+    fn evaluate(&self, (_, a): (S, A)) -> f64 { self.probabilities()[*a.borrow()] }
+}
+
+impl<S> Policy<S> for EXP3 {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, _: S) -> usize {
+        sample_probs_with_rng(rng, &self.probabilities())
+    }
+
+    fn mode(&self, _: S) -> usize { argmax_first(self.log_weights.iter().cloned()).0 }
+}
+
+impl<'m, S> Handler<&'m Transition<S, usize>> for EXP3 {
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<(), ()> {
+        let k = self.log_weights.len() as f64;
+        let p = self.probabilities()[t.action];
+
+        self.log_weights[t.action] += self.gamma * t.reward / p / k;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,344 @@
+use super::{greedy_prob, sampled_prob};
+use crate::{
+    domains::Transition,
+    fa::linear::{basis::Basis, Features},
+    policies::Policy,
+    spaces::Space,
+    utils::{argmax_choose_rng, argmax_first},
+    Function,
+    Handler,
+};
+use ndarray::{Array1, Array2};
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+
+fn project<S, B: Basis<S, Value = Features>>(basis: &B, s: S) -> Array1<f64> {
+    basis.project(s).unwrap().into_dense()
+}
+
+/// Independent ridge regressions of the reward of each arm onto a common set
+/// of features.
+///
+/// The inverse design matrix of each arm, `A⁻¹ = (λI + Σ xxᵀ)⁻¹`, is
+/// maintained directly via the Sherman-Morrison formula, so updates cost
+/// `O(d²)` and no matrix inversion is ever required.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct RidgeArms {
+    pub lambda: f64,
+
+    a_inv: Vec<Array2<f64>>,
+    b: Vec<Array1<f64>>,
+    theta: Vec<Array1<f64>>,
+}
+
+impl RidgeArms {
+    pub fn new(n_arms: usize, n_features: usize, lambda: f64) -> Self {
+        RidgeArms {
+            lambda,
+
+            a_inv: vec![Array2::eye(n_features) / lambda; n_arms],
+            b: vec![Array1::zeros(n_features); n_arms],
+            theta: vec![Array1::zeros(n_features); n_arms],
+        }
+    }
+
+    pub fn n_arms(&self) -> usize { self.theta.len() }
+
+    /// Return the ridge estimate of the weights of `arm`.
+    pub fn weights(&self, arm: usize) -> &Array1<f64> { &self.theta[arm] }
+
+    /// Return the estimated reward of `arm` given features `x`.
+    pub fn mean(&self, arm: usize, x: &Array1<f64>) -> f64 { self.theta[arm].dot(x) }
+
+    /// Return the width, `sqrt(xᵀ A⁻¹ x)`, of the confidence ellipsoid of
+    /// `arm` in the direction of `x`.
+    pub fn width(&self, arm: usize, x: &Array1<f64>) -> f64 {
+        x.dot(&self.a_inv[arm].dot(x)).max(0.0).sqrt()
+    }
+
+    /// Incorporate a `reward` received from pulling `arm` with features `x`.
+    pub fn update(&mut self, arm: usize, x: &Array1<f64>, reward: f64) {
+        let u = self.a_inv[arm].dot(x);
+        let denom = 1.0 + x.dot(&u);
+
+        self.a_inv[arm]
+            .outer_iter_mut()
+            .zip(u.iter())
+            .for_each(|(mut row, ui)| row.scaled_add(-ui / denom, &u));
+
+        self.b[arm].scaled_add(reward, x);
+        self.theta[arm] = self.a_inv[arm].dot(&self.b[arm]);
+    }
+}
+
+/// Linear upper confidence bound (LinUCB) contextual bandit agent.
+///
+/// The state is projected onto `basis` and each arm is scored by
+/// `θ_iᵀx + alpha sqrt(xᵀ A_i⁻¹ x)`, where `θ_i` and `A_i` are given by a
+/// ridge regression of the arm's rewards, `RidgeArms`. The regularisation
+/// strength, `lambda`, defaults to `1`.
+///
+/// # References
+/// - Li, L., Chu, W., Langford, J., Schapire, R. E. (2010). A
+/// contextual-bandit approach to personalized news article recommendation. In
+/// Proceedings of WWW, pp. 661-670.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct LinUCB<B> {
+    pub basis: B,
+    pub arms: RidgeArms,
+
+    pub alpha: f64,
+}
+
+impl<B: Space> LinUCB<B> {
+    pub fn new(basis: B, n_arms: usize, alpha: f64) -> Self {
+        let n_features = basis.dim().into();
+
+        LinUCB {
+            basis,
+            arms: RidgeArms::new(n_arms, n_features, 1.0),
+
+            alpha,
+        }
+    }
+
+    pub fn with_regularisation(self, lambda: f64) -> Self {
+        let n_features = self.basis.dim().into();
+        let arms = RidgeArms::new(self.arms.n_arms(), n_features, lambda);
+
+        LinUCB { arms, ..self }
+    }
+
+    /// Return the upper confidence bound of each arm in state `s`.
+    pub fn indices<S>(&self, s: S) -> Vec<f64>
+    where B: Basis<S, Value = Features> {
+        let x = project(&self.basis, s);
+
+        (0..self.arms.n_arms())
+            .map(|i| self.arms.mean(i, &x) + self.alpha * self.arms.width(i, &x))
+            .collect()
+    }
+}
+
+impl<S, A, B> Function<(S, A)> for LinUCB<B>
+where
+    A: std::borrow::Borrow<usize>,
+    B: Basis<S, Value = Features>,
+{
+    type Output = f64;
+
+    fn evaluate(&self, (s, a): (S, A)) -> f64 { greedy_prob(self.indices(s), *a.borrow()) }
+}
+
+impl<S, B: Basis<S, Value = Features>> Policy<S> for LinUCB<B> {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: S) -> usize {
+        argmax_choose_rng(rng, self.indices(s)).0
+    }
+
+    fn mode(&self, s: S) -> usize { argmax_first(self.indices(s)).0 }
+}
+
+impl<'m, S, B> Handler<&'m Transition<S, usize>> for LinUCB<B>
+where B: Basis<&'m S, Value = Features>
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<(), ()> {
+        let x = project(&self.basis, t.from.state());
+
+        self.arms.update(t.action, &x, t.reward);
+
+        Ok(())
+    }
+}
+
+/// Linear Thompson sampling contextual bandit agent.
+///
+/// Each arm maintains a Gaussian posterior, `N(θ_i, v² A_i⁻¹)`, over the
+/// weights of a ridge regression of its rewards onto the features of the
+/// state. Since only the score `θᵀx` is needed to act, it is sampled directly
+/// from its marginal, `N(θ_iᵀx, v² xᵀ A_i⁻¹ x)`, avoiding any matrix
+/// factorisation. The posterior scale, `v`, is given by `scale`. Action
+/// probabilities have no closed form, so `evaluate` returns a Monte Carlo
+/// estimate of the probability that each arm's sampled score is the largest.
+///
+/// # References
+/// - Agrawal, S., Goyal, N. (2013). Thompson sampling for contextual bandits
+/// with linear payoffs. In Proceedings of ICML, pp. 127-135.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct LinearThompson<B> {
+    pub basis: B,
+    pub arms: RidgeArms,
+
+    pub scale: f64,
+}
+
+impl<B: Space> LinearThompson<B> {
+    pub fn new(basis: B, n_arms: usize, scale: f64) -> Self {
+        let n_features = basis.dim().into();
+
+        LinearThompson {
+            basis,
+            arms: RidgeArms::new(n_arms, n_features, 1.0),
+
+            scale,
+        }
+    }
+
+    pub fn with_regularisation(self, lambda: f64) -> Self {
+        let n_features = self.basis.dim().into();
+        let arms = RidgeArms::new(self.arms.n_arms(), n_features, lambda);
+
+        LinearThompson { arms, ..self }
+    }
+
+    /// Draw a sample of the score of each arm given features `x`.
+    pub fn draw<R: Rng + ?Sized>(&self, rng: &mut R, x: &Array1<f64>) -> Vec<f64> {
+        (0..self.arms.n_arms())
+            .map(|i| {
+                let z: f64 = StandardNormal.sample(rng);
+
+                self.arms.mean(i, x) + self.scale * self.arms.width(i, x) * z
+            })
+            .collect()
+    }
+}
+
+impl<S, A, B> Function<(S, A)> for LinearThompson<B>
+where
+    A: std::borrow::Borrow<usize>,
+    B: Basis<S, Value = Features>,
+{
+    type Output = f64;
+
+    fn evaluate(&self, (s, a): (S, A)) -> f64 {
+        let x = project(&self.basis, s);
+
+        sampled_prob(*a.borrow(), |rng| self.draw(rng, &x))
+    }
+}
+
+impl<S, B: Basis<S, Value = Features>> Policy<S> for LinearThompson<B> {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: S) -> usize {
+        let x = project(&self.basis, s);
+        let samples = self.draw(rng, &x);
+
+        argmax_choose_rng(rng, samples).0
+    }
+
+    fn mode(&self, s: S) -> usize {
+        let x = project(&self.basis, s);
+
+        argmax_first((0..self.arms.n_arms()).map(|i| self.arms.mean(i, &x))).0
+    }
+}
+
+impl<'m, S, B> Handler<&'m Transition<S, usize>> for LinearThompson<B>
+where B: Basis<&'m S, Value = Features>
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<(), ()> {
+        let x = project(&self.basis, t.from.state());
+
+        self.arms.update(t.action, &x, t.reward);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::{
+            bandits::{ContextualBandit, LinearContextual},
+            Domain,
+        },
+        fa::linear::basis::Polynomial,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_ridge_arms() {
+        let mut arms = RidgeArms::new(1, 2, 1.0);
+        let x = Array1::from(vec![1.0, 0.0]);
+
+        arms.update(0, &x, 2.0);
+
+        // A = diag(2, 1), b = [2, 0] => θ = [1, 0].
+        assert!((arms.weights(0)[0] - 1.0).abs() < 1e-10);
+        assert!(arms.weights(0)[1].abs() < 1e-10);
+        assert!((arms.width(0, &x) - 0.5f64.sqrt()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_thompson_probabilities() {
+        let mut agent = LinearThompson::new(Polynomial::new(1, 1), 3, 0.1);
+        let x = project(&agent.basis, &vec![0.5]);
+
+        for _ in 0..20 {
+            agent.arms.update(1, &x, 1.0);
+        }
+
+        let ps: Vec<f64> = (0..3).map(|a| agent.evaluate((&vec![0.5], a))).collect();
+
+        assert!((ps.iter().sum::<f64>() - 1.0).abs() < 1e-10);
+        assert!(ps[1] > 0.99);
+        assert!((ps[0] - ps[2]).abs() < 0.05);
+    }
+
+    fn accuracy<P>(agent: &mut P) -> f64
+    where P: for<'s> Policy<&'s Vec<f64>, Action = usize>
+            + for<'m> Handler<&'m Transition<Vec<f64>, usize>> {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut env =
+            LinearContextual::new(vec![vec![1.0, 0.0], vec![-1.0, 0.5]], 0.1).with_seed(0);
+
+        for _ in 0..500 {
+            let a = agent.sample(&mut rng, env.emit().state());
+            let t = env.transition(a);
+
+            agent.handle(&t).ok();
+        }
+
+        (0..200)
+            .filter(|_| {
+                let best = argmax_first(env.expected_rewards()).0;
+                let a = agent.mode(env.emit().state());
+
+                env.step(&a);
+
+                a == best
+            })
+            .count() as f64
+            / 200.0
+    }
+
+    #[test]
+    fn test_linear_best_arm() {
+        assert!(accuracy(&mut LinUCB::new(Polynomial::new(2, 1), 2, 1.0)) > 0.9);
+        assert!(accuracy(&mut LinearThompson::new(Polynomial::new(2, 1), 2, 0.5)) > 0.9);
+    }
+}
@@ -0,0 +1,153 @@
+//! Multi-armed bandit algorithms.
+//!
+//! Each agent is a `Policy` over arms, `Action = usize`, which learns from the
+//! rewards of `Transition`s passed to `Handler::handle`. The context-free
+//! agents ignore the state they are given, and may therefore be used as
+//! state-independent exploration policies elsewhere; the linear agents
+//! instead project the state onto a `fa::linear` basis. All of them may be run
+//! directly against the domains in `rsrl_domains::bandits`.
+use crate::utils::argmaxima;
+use rand::{rngs::StdRng, SeedableRng};
+
+mod epsilon_greedy;
+mod exp3;
+mod linear;
+mod thompson;
+mod ucb;
+
+pub use self::epsilon_greedy::EpsilonGreedy;
+pub use self::exp3::EXP3;
+pub use self::linear::{LinUCB, LinearThompson, RidgeArms};
+pub use self::thompson::{BetaThompson, GaussianThompson};
+pub use self::ucb::{KLUCB, UCB1, UCBV};
+
+/// Running reward statistics of each arm.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct ArmStatistics {
+    pub counts: Vec<usize>,
+    pub means: Vec<f64>,
+
+    m2: Vec<f64>,
+}
+
+impl ArmStatistics {
+    pub fn new(n_arms: usize) -> Self {
+        ArmStatistics {
+            counts: vec![0; n_arms],
+            means: vec![0.0; n_arms],
+
+            m2: vec![0.0; n_arms],
+        }
+    }
+
+    pub fn n_arms(&self) -> usize { self.counts.len() }
+
+    /// Return the total number of pulls across all arms.
+    pub fn n_pulls(&self) -> usize { self.counts.iter().sum() }
+
+    /// Return the empirical (biased) variance of the rewards of `arm`.
+    pub fn variance(&self, arm: usize) -> f64 {
+        if self.counts[arm] > 0 {
+            self.m2[arm] / self.counts[arm] as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Incorporate a `reward` received from pulling `arm`.
+    pub fn update(&mut self, arm: usize, reward: f64) {
+        self.counts[arm] += 1;
+
+        let delta = reward - self.means[arm];
+
+        self.means[arm] += delta / self.counts[arm] as f64;
+        self.m2[arm] += delta * (reward - self.means[arm]);
+    }
+}
+
+/// Return the probability of choosing `arm` when acting greedily with respect
+/// to `values`, breaking ties uniformly at random.
+fn greedy_prob(values: Vec<f64>, arm: usize) -> f64 {
+    let (maxima, _) = argmaxima(values);
+
+    if maxima.contains(&arm) {
+        1.0 / maxima.len() as f64
+    } else {
+        0.0
+    }
+}
+
+/// Number of posterior draws used by `sampled_prob`.
+const N_POSTERIOR_DRAWS: usize = 1000;
+
+/// Return a Monte Carlo estimate of the probability that `arm` is the argmax
+/// of the scores returned by `draw`, breaking ties uniformly at random.
+///
+/// The draws are generated from a fixed seed, so the estimate is deterministic
+/// and, for a given `draw`, sums to one across arms.
+fn sampled_prob<F>(arm: usize, mut draw: F) -> f64
+where F: FnMut(&mut StdRng) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    (0..N_POSTERIOR_DRAWS)
+        .map(|_| greedy_prob(draw(&mut rng), arm))
+        .sum::<f64>()
+        / N_POSTERIOR_DRAWS as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::{bandits::Bernoulli, Domain},
+        policies::Policy,
+        Handler,
+    };
+
+    fn run<P>(agent: &mut P, n_pulls: usize) -> Vec<usize>
+    where P: for<'s> Policy<&'s usize, Action = usize>
+            + for<'m> Handler<&'m crate::domains::Transition<usize, usize>>
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut env = Bernoulli::new(vec![0.2, 0.8]).with_seed(0);
+        let mut counts = vec![0; 2];
+
+        for _ in 0..n_pulls {
+            let a = agent.sample(&mut rng, env.emit().state());
+            let t = env.transition(a);
+
+            counts[a] += 1;
+            agent.handle(&t).ok();
+        }
+
+        counts
+    }
+
+    #[test]
+    fn test_arm_statistics() {
+        let mut stats = ArmStatistics::new(2);
+
+        stats.update(1, 1.0);
+        stats.update(1, 3.0);
+
+        assert_eq!(stats.n_pulls(), 2);
+        assert_eq!(stats.means, vec![0.0, 2.0]);
+        assert_eq!(stats.variance(1), 1.0);
+    }
+
+    #[test]
+    fn test_best_arm() {
+        assert!(run(&mut EpsilonGreedy::new(2, 0.1), 1000)[1] > 700);
+        assert!(run(&mut UCB1::new(2), 1000)[1] > 700);
+        assert!(run(&mut UCBV::new(2), 1000)[1] > 700);
+        assert!(run(&mut KLUCB::new(2), 1000)[1] > 700);
+        assert!(run(&mut BetaThompson::new(2), 1000)[1] > 700);
+        assert!(run(&mut GaussianThompson::new(2), 1000)[1] > 700);
+        assert!(run(&mut EXP3::new(2, 0.1), 1000)[1] > 600);
+    }
+}
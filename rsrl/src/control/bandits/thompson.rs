@@ -0,0 +1,232 @@
+use super::{sampled_prob, ArmStatistics};
+use crate::{
+    domains::Transition,
+    policies::Policy,
+    utils::{argmax_choose_rng, argmax_first},
+    Function,
+    Handler,
+};
+use rand::Rng;
+use rand_distr::{Beta, Distribution, Normal};
+
+/// Thompson sampling with Beta posteriors over the mean of each arm.
+///
+/// Rewards are assumed to lie in `[0, 1]`; a reward `r` adds `r` to the
+/// successes and `1 - r` to the failures of the pulled arm. Action
+/// probabilities have no closed form, so `evaluate` returns a Monte Carlo
+/// estimate of the probability that each arm's posterior draw is the largest.
+///
+/// # References
+/// - Thompson, W. R. (1933). On the likelihood that one unknown probability
+/// exceeds another in view of the evidence of two samples. Biometrika,
+/// 25(3-4), 285-294.
+/// - Agrawal, S., Goyal, N. (2012). Analysis of Thompson sampling for the
+/// multi-armed bandit problem. In Proceedings of COLT, pp. 39.1-39.26.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct BetaThompson {
+    pub successes: Vec<f64>,
+    pub failures: Vec<f64>,
+}
+
+impl BetaThompson {
+    pub fn new(n_arms: usize) -> Self { BetaThompson::with_prior(n_arms, 1.0, 1.0) }
+
+    pub fn with_prior(n_arms: usize, alpha: f64, beta: f64) -> Self {
+        BetaThompson {
+            successes: vec![alpha; n_arms],
+            failures: vec![beta; n_arms],
+        }
+    }
+
+    /// Return the posterior mean of each arm.
+    pub fn posterior_means(&self) -> Vec<f64> {
+        self.successes
+            .iter()
+            .zip(self.failures.iter())
+            .map(|(a, b)| a / (a + b))
+            .collect()
+    }
+
+    /// Draw a sample from the posterior of each arm.
+    pub fn draw<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        self.successes
+            .iter()
+            .zip(self.failures.iter())
+            .map(|(&a, &b)| Beta::new(a, b).unwrap().sample(rng))
+            .collect()
+    }
+}
+
+impl<S, A: std::borrow::Borrow<usize>> Function<(S, A)> for BetaThompson {
+    type Output = f64;
+
+    fn evaluate(&self, (_, a): (S, A)) -> f64 { sampled_prob(*a.borrow(), |rng| self.draw(rng)) }
+}
+
+impl<S> Policy<S> for BetaThompson {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, _: S) -> usize {
+        let samples = self.draw(rng);
+
+        argmax_choose_rng(rng, samples).0
+    }
+
+    fn mode(&self, _: S) -> usize { argmax_first(self.posterior_means()).0 }
+}
+
+impl<'m, S> Handler<&'m Transition<S, usize>> for BetaThompson {
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<(), ()> {
+        let r = t.reward.clamp(0.0, 1.0);
+
+        self.successes[t.action] += r;
+        self.failures[t.action] += 1.0 - r;
+
+        Ok(())
+    }
+}
+
+/// Thompson sampling with Gaussian posteriors over the mean of each arm.
+///
+/// Each arm is given a `N(prior_mean, prior_stddev²)` prior, and rewards are
+/// assumed to be Gaussian with known standard deviation, `noise_stddev`, such
+/// that the posterior remains Gaussian. Action probabilities have no closed
+/// form, so `evaluate` returns a Monte Carlo estimate of the probability that
+/// each arm's posterior draw is the largest.
+///
+/// # References
+/// - Agrawal, S., Goyal, N. (2013). Further optimal regret bounds for Thompson
+/// sampling. In Proceedings of AISTATS, pp. 99-107.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct GaussianThompson {
+    pub stats: ArmStatistics,
+
+    pub prior_mean: f64,
+    pub prior_stddev: f64,
+    pub noise_stddev: f64,
+}
+
+impl GaussianThompson {
+    pub fn new(n_arms: usize) -> Self { GaussianThompson::with_prior(n_arms, 0.0, 1.0, 1.0) }
+
+    pub fn with_prior(
+        n_arms: usize,
+        prior_mean: f64,
+        prior_stddev: f64,
+        noise_stddev: f64,
+    ) -> Self
+    {
+        GaussianThompson {
+            stats: ArmStatistics::new(n_arms),
+
+            prior_mean,
+            prior_stddev,
+            noise_stddev,
+        }
+    }
+
+    /// Return the mean and standard deviation of the posterior of `arm`.
+    pub fn posterior(&self, arm: usize) -> (f64, f64) {
+        let prior_precision = self.prior_stddev.powi(-2);
+        let noise_precision = self.noise_stddev.powi(-2);
+
+        let n = self.stats.counts[arm] as f64;
+        let precision = prior_precision + n * noise_precision;
+        let mean = (prior_precision * self.prior_mean
+            + n * noise_precision * self.stats.means[arm])
+            / precision;
+
+        (mean, precision.sqrt().recip())
+    }
+
+    /// Draw a sample from the posterior of each arm.
+    pub fn draw<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        (0..self.stats.n_arms())
+            .map(|i| {
+                let (mean, stddev) = self.posterior(i);
+
+                Normal::new(mean, stddev).unwrap().sample(rng)
+            })
+            .collect()
+    }
+}
+
+impl<S, A: std::borrow::Borrow<usize>> Function<(S, A)> for GaussianThompson {
+    type Output = f64;
+
+    fn evaluate(&self, (_, a): (S, A)) -> f64 { sampled_prob(*a.borrow(), |rng| self.draw(rng)) }
+}
+
+impl<S> Policy<S> for GaussianThompson {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, _: S) -> usize {
+        let samples = self.draw(rng);
+
+        argmax_choose_rng(rng, samples).0
+    }
+
+    fn mode(&self, _: S) -> usize {
+        argmax_first((0..self.stats.n_arms()).map(|i| self.posterior(i).0)).0
+    }
+}
+
+impl<'m, S> Handler<&'m Transition<S, usize>> for GaussianThompson {
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<(), ()> {
+        self.stats.update(t.action, t.reward);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probs<P: Policy<(), Action = usize>>(p: &P, n_arms: usize) -> Vec<f64> {
+        (0..n_arms).map(|a| p.evaluate(((), a))).collect()
+    }
+
+    #[test]
+    fn test_beta_probabilities() {
+        let mut agent = BetaThompson::new(3);
+
+        agent.successes[2] = 50.0;
+
+        let ps = probs(&agent, 3);
+
+        assert!((ps.iter().sum::<f64>() - 1.0).abs() < 1e-10);
+        assert!(ps[2] > 0.9);
+        assert!((ps[0] - ps[1]).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_gaussian_probabilities() {
+        let mut agent = GaussianThompson::new(3);
+
+        for _ in 0..10 {
+            agent.stats.update(1, 5.0);
+        }
+
+        let ps = probs(&agent, 3);
+
+        assert!((ps.iter().sum::<f64>() - 1.0).abs() < 1e-10);
+        assert!(ps[1] > 0.99);
+    }
+}
@@ -0,0 +1,208 @@
+use super::{greedy_prob, ArmStatistics};
+use crate::{
+    domains::Transition,
+    policies::Policy,
+    utils::{argmax_choose_rng, argmax_first},
+    Function,
+    Handler,
+};
+use rand::Rng;
+use std::f64;
+
+macro_rules! impl_index_policy {
+    ($type:ident) => {
+        impl<S, A: std::borrow::Borrow<usize>> Function<(S, A)> for $type {
+            type Output = f64;
+
+            fn evaluate(&self, (_, a): (S, A)) -> f64 { greedy_prob(self.indices(), *a.borrow()) }
+        }
+
+        impl<S> Policy<S> for $type {
+            type Action = usize;
+
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R, _: S) -> usize {
+                argmax_choose_rng(rng, self.indices()).0
+            }
+
+            fn mode(&self, _: S) -> usize { argmax_first(self.indices()).0 }
+        }
+
+        impl<'m, S> Handler<&'m Transition<S, usize>> for $type {
+            type Response = ();
+            type Error = ();
+
+            fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<(), ()> {
+                self.stats.update(t.action, t.reward);
+
+                Ok(())
+            }
+        }
+    };
+}
+
+/// Upper confidence bound (UCB1) bandit agent.
+///
+/// Each arm is scored by `μ_i + sqrt(c ln t / n_i)`, where `c = 2` recovers
+/// the original algorithm for rewards in `[0, 1]`. Arms that have not yet been
+/// pulled are always preferred.
+///
+/// # References
+/// - Auer, P., Cesa-Bianchi, N., Fischer, P. (2002). Finite-time analysis of
+/// the multiarmed bandit problem. Machine Learning, 47(2-3), 235-256.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct UCB1 {
+    pub stats: ArmStatistics,
+    pub c: f64,
+}
+
+impl UCB1 {
+    pub fn new(n_arms: usize) -> Self {
+        UCB1 {
+            stats: ArmStatistics::new(n_arms),
+            c: 2.0,
+        }
+    }
+
+    /// Return the upper confidence bound of each arm.
+    pub fn indices(&self) -> Vec<f64> {
+        let ln_t = (self.stats.n_pulls().max(1) as f64).ln();
+
+        (0..self.stats.n_arms())
+            .map(|i| match self.stats.counts[i] {
+                0 => f64::MAX,
+                n => self.stats.means[i] + (self.c * ln_t / n as f64).sqrt(),
+            })
+            .collect()
+    }
+}
+
+impl_index_policy!(UCB1);
+
+/// Variance-aware upper confidence bound (UCB-V) bandit agent.
+///
+/// Each arm is scored by `μ_i + sqrt(2 V_i E / n_i) + 3 b E / n_i`, with
+/// `E = zeta ln t`, where `V_i` is the empirical variance of the arm and `b`
+/// is the `range` of the rewards. This yields tighter bounds than `UCB1` for
+/// arms with low variance.
+///
+/// # References
+/// - Audibert, J.-Y., Munos, R., Szepesvári, C. (2009). Exploration-exploitation
+/// tradeoff using variance estimates in multi-armed bandits. Theoretical
+/// Computer Science, 410(19), 1876-1902.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct UCBV {
+    pub stats: ArmStatistics,
+    pub range: f64,
+    pub zeta: f64,
+}
+
+impl UCBV {
+    pub fn new(n_arms: usize) -> Self {
+        UCBV {
+            stats: ArmStatistics::new(n_arms),
+            range: 1.0,
+            zeta: 1.2,
+        }
+    }
+
+    /// Return the upper confidence bound of each arm.
+    pub fn indices(&self) -> Vec<f64> {
+        let e = self.zeta * (self.stats.n_pulls().max(1) as f64).ln();
+
+        (0..self.stats.n_arms())
+            .map(|i| match self.stats.counts[i] {
+                0 => f64::MAX,
+                n => {
+                    let n = n as f64;
+
+                    self.stats.means[i]
+                        + (2.0 * self.stats.variance(i) * e / n).sqrt()
+                        + 3.0 * self.range * e / n
+                },
+            })
+            .collect()
+    }
+}
+
+impl_index_policy!(UCBV);
+
+/// Kullback-Leibler divergence between Bernoulli distributions with means `p`
+/// and `q`.
+fn bernoulli_kl(p: f64, q: f64) -> f64 {
+    const EPS: f64 = 1e-12;
+
+    let p = p.clamp(EPS, 1.0 - EPS);
+    let q = q.clamp(EPS, 1.0 - EPS);
+
+    p * (p / q).ln() + (1.0 - p) * ((1.0 - p) / (1.0 - q)).ln()
+}
+
+/// Kullback-Leibler upper confidence bound (KL-UCB) bandit agent.
+///
+/// Each arm is scored by the largest mean `q ≥ μ_i` satisfying
+/// `n_i kl(μ_i, q) ≤ ln t + c ln ln t`, where `kl` is the Bernoulli divergence;
+/// rewards are therefore assumed to lie in `[0, 1]`. The bound is found by
+/// bisection.
+///
+/// # References
+/// - Garivier, A., Cappé, O. (2011). The KL-UCB algorithm for bounded
+/// stochastic bandits and beyond. In Proceedings of COLT, pp. 359-376.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct KLUCB {
+    pub stats: ArmStatistics,
+    pub c: f64,
+}
+
+impl KLUCB {
+    pub fn new(n_arms: usize) -> Self {
+        KLUCB {
+            stats: ArmStatistics::new(n_arms),
+            c: 0.0,
+        }
+    }
+
+    /// Return the upper confidence bound of each arm.
+    pub fn indices(&self) -> Vec<f64> {
+        let ln_t = (self.stats.n_pulls().max(1) as f64).ln();
+        let budget = ln_t + self.c * ln_t.ln().max(0.0);
+
+        (0..self.stats.n_arms())
+            .map(|i| match self.stats.counts[i] {
+                0 => f64::MAX,
+                n => {
+                    let mean = self.stats.means[i].clamp(0.0, 1.0);
+                    let (mut lo, mut hi) = (mean, 1.0);
+
+                    for _ in 0..32 {
+                        let mid = (lo + hi) / 2.0;
+
+                        if n as f64 * bernoulli_kl(mean, mid) > budget {
+                            hi = mid;
+                        } else {
+                            lo = mid;
+                        }
+                    }
+
+                    lo
+                },
+            })
+            .collect()
+    }
+}
+
+impl_index_policy!(KLUCB);
@@ -0,0 +1,108 @@
+use super::{ranking, standard_normal, Strategy};
+use ndarray::Array1;
+use rand::Rng;
+
+/// Cross-entropy method with a diagonal Gaussian search distribution.
+///
+/// A `population` of candidates is sampled each generation, after which the
+/// mean and per-dimension standard deviation are refit to the `n_elite`
+/// fittest. A constant `noise` term is added to the refit variance to prevent
+/// premature collapse of the distribution.
+///
+/// # References
+/// - Rubinstein, R. Y., Kroese, D. P. (2004). The Cross-Entropy Method: A
+/// Unified Approach to Combinatorial Optimization, Monte-Carlo Simulation and
+/// Machine Learning. Springer-Verlag.
+/// - Szita, I., Lörincz, A. (2006). Learning Tetris using the noisy
+/// cross-entropy method. Neural Computation, 18(12), 2936-2941.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct CEM {
+    pub mean: Array1<f64>,
+    pub stddev: Array1<f64>,
+
+    pub population: usize,
+    pub n_elite: usize,
+    pub noise: f64,
+}
+
+impl CEM {
+    pub fn new(mean: Array1<f64>, stddev: f64, population: usize, n_elite: usize) -> Self {
+        let stddev = Array1::from_elem(mean.len(), stddev);
+
+        CEM {
+            mean,
+            stddev,
+
+            population,
+            n_elite,
+            noise: 0.0,
+        }
+    }
+
+    pub fn with_noise(self, noise: f64) -> Self { CEM { noise, ..self } }
+}
+
+impl Strategy for CEM {
+    fn ask<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<Array1<f64>> {
+        (0..self.population)
+            .map(|_| &self.mean + &(standard_normal(rng, self.mean.len()) * &self.stddev))
+            .collect()
+    }
+
+    fn tell(&mut self, candidates: &[Array1<f64>], fitness: &[f64]) {
+        let elite: Vec<&Array1<f64>> = ranking(fitness)
+            .into_iter()
+            .take(self.n_elite.max(1))
+            .map(|i| &candidates[i])
+            .collect();
+        let n = elite.len() as f64;
+
+        let mean: Array1<f64> = elite
+            .iter()
+            .fold(Array1::zeros(self.mean.len()), |acc, &x| acc + x)
+            / n;
+        let var: Array1<f64> = elite
+            .iter()
+            .fold(Array1::zeros(self.mean.len()), |acc, &x| {
+                acc + (x - &mean).mapv_into(|d| d * d)
+            })
+            / n;
+
+        self.stddev = var.mapv_into(|v| (v + self.noise).sqrt());
+        self.mean = mean;
+    }
+
+    fn solution(&self) -> Array1<f64> { self.mean.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_quadratic() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut cem = CEM::new(Array1::zeros(2), 1.0, 50, 10).with_noise(1e-4);
+
+        for _ in 0..50 {
+            let candidates = cem.ask(&mut rng);
+            let fitness: Vec<f64> = candidates
+                .iter()
+                .map(|x| -(x[0] - 1.0).powi(2) - (x[1] + 2.0).powi(2))
+                .collect();
+
+            cem.tell(&candidates, &fitness);
+        }
+
+        let x = cem.solution();
+
+        assert!((x[0] - 1.0).abs() < 0.1);
+        assert!((x[1] + 2.0).abs() < 0.1);
+    }
+}
@@ -0,0 +1,214 @@
+use super::{ranking, standard_normal, Strategy};
+use ndarray::{Array1, Array2, Axis};
+use ndarray_linalg::{Eigh, UPLO};
+use rand::Rng;
+
+/// Covariance matrix adaptation evolution strategy.
+///
+/// Candidates are drawn from `N(mean, sigma² C)`, where the covariance `C` is
+/// adapted from the weighted recombination of the fittest half of the
+/// population and an evolution path, and the global step size `sigma` is
+/// controlled by cumulative step-size adaptation. The default population size
+/// is `4 + ⌊3 ln n⌋`, and all other constants follow Hansen (2016).
+///
+/// # References
+/// - Hansen, N., Ostermeier, A. (2001). Completely derandomized
+/// self-adaptation in evolution strategies. Evolutionary Computation, 9(2),
+/// 159-195.
+/// - Hansen, N. (2016). The CMA evolution strategy: A tutorial.
+/// arXiv:1604.00772.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct CMAES {
+    pub mean: Array1<f64>,
+    pub sigma: f64,
+
+    population: usize,
+    weights: Array1<f64>,
+    mu_eff: f64,
+
+    c_sigma: f64,
+    d_sigma: f64,
+    c_c: f64,
+    c_1: f64,
+    c_mu: f64,
+    chi_n: f64,
+
+    cov: Array2<f64>,
+    eigvecs: Array2<f64>,
+    eigvals: Array1<f64>,
+
+    p_sigma: Array1<f64>,
+    p_c: Array1<f64>,
+
+    generation: usize,
+}
+
+impl CMAES {
+    pub fn new(mean: Array1<f64>, sigma: f64) -> Self {
+        let n = mean.len().max(1) as f64;
+        let population = 4 + (3.0 * n.ln()).floor() as usize;
+
+        CMAES::with_population(mean, sigma, population)
+    }
+
+    pub fn with_population(mean: Array1<f64>, sigma: f64, population: usize) -> Self {
+        let dim = mean.len();
+        let n = dim.max(1) as f64;
+        let population = population.max(2);
+
+        let mu = population / 2;
+        let raw: Array1<f64> = (1..=mu)
+            .map(|i| (mu as f64 + 0.5).ln() - (i as f64).ln())
+            .collect();
+        let weights = &raw / raw.sum();
+        let mu_eff = 1.0 / weights.iter().fold(0.0, |acc, w| acc + w * w);
+
+        let c_sigma = (mu_eff + 2.0) / (n + mu_eff + 5.0);
+        let d_sigma = 1.0 + 2.0 * (((mu_eff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0) + c_sigma;
+        let c_c = (4.0 + mu_eff / n) / (n + 4.0 + 2.0 * mu_eff / n);
+        let c_1 = 2.0 / ((n + 1.3).powi(2) + mu_eff);
+        let c_mu =
+            (1.0 - c_1).min(2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((n + 2.0).powi(2) + mu_eff));
+        let chi_n = n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n));
+
+        CMAES {
+            mean,
+            sigma,
+
+            population,
+            weights,
+            mu_eff,
+
+            c_sigma,
+            d_sigma,
+            c_c,
+            c_1,
+            c_mu,
+            chi_n,
+
+            cov: Array2::eye(dim),
+            eigvecs: Array2::eye(dim),
+            eigvals: Array1::ones(dim),
+
+            p_sigma: Array1::zeros(dim),
+            p_c: Array1::zeros(dim),
+
+            generation: 0,
+        }
+    }
+
+    /// Recompute the eigendecomposition `C = B D² Bᵀ`, keeping the previous
+    /// factors if the decomposition fails.
+    fn decompose(&mut self) {
+        let sym = (&self.cov + &self.cov.t()) / 2.0;
+
+        if let Ok((d2, b)) = sym.eigh(UPLO::Upper) {
+            self.cov = sym;
+            self.eigvals = d2.mapv_into(|v| v.max(1e-20).sqrt());
+            self.eigvecs = b;
+        }
+    }
+}
+
+impl Strategy for CMAES {
+    fn ask<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<Array1<f64>> {
+        (0..self.population)
+            .map(|_| {
+                let y = self.eigvecs.dot(&(standard_normal(rng, self.mean.len()) * &self.eigvals));
+
+                &self.mean + &(y * self.sigma)
+            })
+            .collect()
+    }
+
+    fn tell(&mut self, candidates: &[Array1<f64>], fitness: &[f64]) {
+        let n = self.mean.len() as f64;
+        let ys: Vec<Array1<f64>> = ranking(fitness)
+            .into_iter()
+            .take(self.weights.len())
+            .map(|i| (&candidates[i] - &self.mean) / self.sigma)
+            .collect();
+
+        let mut y_w = Array1::zeros(self.mean.len());
+
+        for (y, &w) in ys.iter().zip(self.weights.iter()) {
+            y_w.scaled_add(w, y);
+        }
+
+        self.mean.scaled_add(self.sigma, &y_w);
+        self.generation += 1;
+
+        // Step-size path, using C^(-1/2) = B D^(-1) Bᵀ:
+        let c_inv_sqrt_y = self.eigvecs.dot(&(self.eigvecs.t().dot(&y_w) / &self.eigvals));
+
+        self.p_sigma *= 1.0 - self.c_sigma;
+        self.p_sigma.scaled_add(
+            (self.c_sigma * (2.0 - self.c_sigma) * self.mu_eff).sqrt(),
+            &c_inv_sqrt_y,
+        );
+
+        let ps_norm = self.p_sigma.iter().fold(0.0, |acc, p| acc + p * p).sqrt();
+        let decay = 1.0 - (1.0 - self.c_sigma).powi(2 * self.generation as i32);
+        let h_sigma = if ps_norm / decay.sqrt() < (1.4 + 2.0 / (n + 1.0)) * self.chi_n {
+            1.0
+        } else {
+            0.0
+        };
+
+        // Covariance path and rank-one/rank-μ updates:
+        self.p_c *= 1.0 - self.c_c;
+        self.p_c
+            .scaled_add(h_sigma * (self.c_c * (2.0 - self.c_c) * self.mu_eff).sqrt(), &y_w);
+
+        let pc = self.p_c.view().insert_axis(Axis(1));
+        let correction = (1.0 - h_sigma) * self.c_c * (2.0 - self.c_c);
+
+        self.cov *= 1.0 - self.c_1 - self.c_mu + self.c_1 * correction;
+        self.cov.scaled_add(self.c_1, &pc.dot(&pc.t()));
+
+        for (y, &w) in ys.iter().zip(self.weights.iter()) {
+            let y = y.view().insert_axis(Axis(1));
+
+            self.cov.scaled_add(self.c_mu * w, &y.dot(&y.t()));
+        }
+
+        self.sigma *= ((self.c_sigma / self.d_sigma) * (ps_norm / self.chi_n - 1.0)).exp();
+
+        self.decompose();
+    }
+
+    fn solution(&self) -> Array1<f64> { self.mean.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_ill_conditioned_quadratic() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut cma = CMAES::new(Array1::zeros(2), 1.0);
+
+        for _ in 0..200 {
+            let candidates = cma.ask(&mut rng);
+            let fitness: Vec<f64> = candidates
+                .iter()
+                .map(|x| -(x[0] - 1.0).powi(2) - 100.0 * (x[1] + 2.0).powi(2))
+                .collect();
+
+            cma.tell(&candidates, &fitness);
+        }
+
+        let x = cma.solution();
+
+        assert!((x[0] - 1.0).abs() < 1e-3);
+        assert!((x[1] + 2.0).abs() < 1e-3);
+        assert!(cma.sigma < 1.0);
+    }
+}
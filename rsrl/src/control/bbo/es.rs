@@ -0,0 +1,214 @@
+use super::{ranking, standard_normal, Strategy};
+use ndarray::Array1;
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+
+/// Self-adaptive (μ, λ) evolution strategy.
+///
+/// Each of the `n_parents` parents carries its own mutation strength. In every
+/// generation, `n_offspring` children are produced by log-normally perturbing
+/// the mutation strength of a parent, at rate `tau`, and then adding isotropic
+/// Gaussian noise of that strength to its weights. The fittest `n_parents`
+/// children, along with their mutation strengths, replace the parents. Since
+/// every parent must be replaced, `n_offspring` is raised to at least
+/// `n_parents`.
+///
+/// # References
+/// - Beyer, H.-G., Schwefel, H.-P. (2002). Evolution strategies - a
+/// comprehensive introduction. Natural Computing, 1(1), 3-52.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct MuLambdaES {
+    pub n_parents: usize,
+    pub n_offspring: usize,
+    pub tau: f64,
+
+    parents: Vec<Array1<f64>>,
+    stddevs: Vec<f64>,
+    offspring_stddevs: Vec<f64>,
+}
+
+impl MuLambdaES {
+    pub fn new(mean: Array1<f64>, stddev: f64, n_parents: usize, n_offspring: usize) -> Self {
+        let n_parents = n_parents.max(1);
+        let n_offspring = n_offspring.max(n_parents);
+        let tau = 1.0 / (mean.len().max(1) as f64).sqrt();
+
+        MuLambdaES {
+            n_parents,
+            n_offspring,
+            tau,
+
+            parents: vec![mean; n_parents],
+            stddevs: vec![stddev; n_parents],
+            offspring_stddevs: vec![],
+        }
+    }
+}
+
+impl Strategy for MuLambdaES {
+    fn ask<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<Array1<f64>> {
+        let n_parents = self.parents.len();
+
+        self.offspring_stddevs.clear();
+
+        (0..self.n_offspring)
+            .map(|i| {
+                let j = i % n_parents;
+                let z: f64 = StandardNormal.sample(rng);
+                let stddev = self.stddevs[j] * (self.tau * z).exp();
+
+                self.offspring_stddevs.push(stddev);
+
+                &self.parents[j] + &(standard_normal(rng, self.parents[j].len()) * stddev)
+            })
+            .collect()
+    }
+
+    fn tell(&mut self, candidates: &[Array1<f64>], fitness: &[f64]) {
+        let selected: Vec<usize> = ranking(fitness).into_iter().take(self.n_parents).collect();
+
+        self.parents = selected.iter().map(|&i| candidates[i].clone()).collect();
+        self.stddevs = selected.iter().map(|&i| self.offspring_stddevs[i]).collect();
+    }
+
+    fn solution(&self) -> Array1<f64> { self.parents[0].clone() }
+}
+
+/// Antithetic evolution strategy with fitness shaping.
+///
+/// The search distribution is an isotropic Gaussian with fixed `stddev`
+/// centred on `mean`. Each generation samples `n_pairs` mirrored perturbations,
+/// `mean ± stddev * ε`, and replaces their returns with centred ranks before
+/// taking a gradient step of size `alpha` along the resulting estimate of the
+/// gradient of the expected fitness.
+///
+/// # References
+/// - Salimans, T., Ho, J., Chen, X., Sidor, S., Sutskever, I. (2017).
+/// Evolution strategies as a scalable alternative to reinforcement learning.
+/// arXiv:1703.03864.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct AntitheticES {
+    pub mean: Array1<f64>,
+    pub stddev: f64,
+
+    pub alpha: f64,
+    pub n_pairs: usize,
+}
+
+impl AntitheticES {
+    pub fn new(mean: Array1<f64>, stddev: f64, alpha: f64, n_pairs: usize) -> Self {
+        AntitheticES {
+            mean,
+            stddev,
+
+            alpha,
+            n_pairs,
+        }
+    }
+}
+
+/// Map each fitness to its rank, rescaled to lie in `[-0.5, 0.5]`.
+fn centred_ranks(fitness: &[f64]) -> Vec<f64> {
+    let n = fitness.len();
+    let scale = (n.max(2) - 1) as f64;
+    let mut ranks = vec![0.0; n];
+
+    for (r, i) in ranking(fitness).into_iter().rev().enumerate() {
+        ranks[i] = r as f64 / scale - 0.5;
+    }
+
+    ranks
+}
+
+impl Strategy for AntitheticES {
+    fn ask<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<Array1<f64>> {
+        (0..self.n_pairs)
+            .flat_map(|_| {
+                let eps = standard_normal(rng, self.mean.len()) * self.stddev;
+
+                vec![&self.mean + &eps, &self.mean - &eps]
+            })
+            .collect()
+    }
+
+    fn tell(&mut self, candidates: &[Array1<f64>], fitness: &[f64]) {
+        let ranks = centred_ranks(fitness);
+        let n = (candidates.len() / 2).max(1) as f64;
+        let scale = self.alpha / (2.0 * n * self.stddev);
+
+        let mut grad = Array1::zeros(self.mean.len());
+
+        for (pair, r) in candidates.chunks(2).zip(ranks.chunks(2)) {
+            if pair.len() == 2 {
+                let eps = (&pair[0] - &self.mean) / self.stddev;
+
+                grad.scaled_add(r[0] - r[1], &eps);
+            }
+        }
+
+        self.mean.scaled_add(scale, &grad);
+    }
+
+    fn solution(&self) -> Array1<f64> { self.mean.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn optimise<O: Strategy>(strategy: &mut O, n_generations: usize) -> Array1<f64> {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..n_generations {
+            let candidates = strategy.ask(&mut rng);
+            let fitness: Vec<f64> = candidates
+                .iter()
+                .map(|x| -(x[0] - 1.0).powi(2) - (x[1] + 2.0).powi(2))
+                .collect();
+
+            strategy.tell(&candidates, &fitness);
+        }
+
+        strategy.solution()
+    }
+
+    #[test]
+    fn test_mu_lambda_quadratic() {
+        let x = optimise(&mut MuLambdaES::new(Array1::zeros(2), 1.0, 5, 20), 100);
+
+        assert!((x[0] - 1.0).abs() < 0.05);
+        assert!((x[1] + 2.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_antithetic_quadratic() {
+        let x = optimise(&mut AntitheticES::new(Array1::zeros(2), 0.1, 0.01, 20), 500);
+
+        assert!((x[0] - 1.0).abs() < 0.1);
+        assert!((x[1] + 2.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_offspring_bound() {
+        let es = MuLambdaES::new(Array1::zeros(2), 1.0, 3, 0);
+
+        assert_eq!(es.n_offspring, 3);
+        assert_eq!(es.solution(), Array1::<f64>::zeros(2));
+    }
+
+    #[test]
+    fn test_centred_ranks() {
+        assert_eq!(centred_ranks(&[3.0, -1.0, 1.0]), vec![0.5, -0.5, 0.0]);
+    }
+}
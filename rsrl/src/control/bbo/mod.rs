@@ -0,0 +1,250 @@
+//! Black-box policy search algorithms.
+//!
+//! The methods in this module treat the flattened weights of a `Parameterised`
+//! policy as a point in ℝⁿ and search for the point maximising the expected
+//! return, as estimated from complete rollouts of a `Domain`. No critic or
+//! gradient information is required.
+use crate::{
+    domains::{Action, Domain, State},
+    params::Parameterised,
+    policies::Policy,
+    Handler,
+};
+use ndarray::Array1;
+use rand::{thread_rng, Rng};
+use rand_distr::{Distribution, StandardNormal};
+use std::thread;
+
+mod cem;
+mod cma_es;
+mod es;
+mod pgpe;
+
+pub use self::cem::CEM;
+pub use self::cma_es::CMAES;
+pub use self::es::{AntitheticES, MuLambdaES};
+pub use self::pgpe::PGPE;
+
+/// Interface for derivative-free optimisers with an ask-tell protocol.
+pub trait Strategy {
+    /// Sample a population of candidate solutions.
+    fn ask<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<Array1<f64>>;
+
+    /// Update the search distribution given the fitness of each candidate
+    /// returned by the preceding call to `ask`.
+    fn tell(&mut self, candidates: &[Array1<f64>], fitness: &[f64]);
+
+    /// Return the current estimate of the optimal solution.
+    fn solution(&self) -> Array1<f64>;
+}
+
+/// Return the weights of `policy` as a flat vector, e.g. to serve as the
+/// initial solution of a `Strategy`.
+pub fn flat_weights<P: Parameterised>(policy: &P) -> Array1<f64> {
+    policy.weights_view().iter().cloned().collect()
+}
+
+/// Return the indices of `fitness` in descending order of value.
+fn ranking(fitness: &[f64]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..fitness.len()).collect();
+
+    indices.sort_by(|&i, &j| fitness[j].total_cmp(&fitness[i]));
+
+    indices
+}
+
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R, n: usize) -> Array1<f64> {
+    Array1::from_shape_fn(n, |_| StandardNormal.sample(rng))
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Response {
+    /// Estimated return of each candidate in the population.
+    pub fitness: Vec<f64>,
+}
+
+/// Black-box search over the weights of a policy.
+///
+/// Each call to `handle` takes a constructor for the `Domain`, samples a
+/// population from the `strategy`, and estimates the fitness of every
+/// candidate as its mean total reward over `n_episodes` rollouts, each of at
+/// most `step_limit` steps. Actions are sampled from `policy`, so stochastic
+/// policies are evaluated in expectation. The search distribution is then
+/// updated and `policy` is assigned the current solution. Candidates are
+/// evaluated on `n_threads` threads, which requires both the policy and the
+/// domain constructor to be `Sync`.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct PolicySearch<P, O> {
+    #[weights]
+    pub policy: P,
+    pub strategy: O,
+
+    pub n_episodes: usize,
+    pub step_limit: Option<usize>,
+    pub n_threads: usize,
+}
+
+impl<P, O> PolicySearch<P, O> {
+    pub fn new(policy: P, strategy: O) -> Self {
+        PolicySearch {
+            policy,
+            strategy,
+
+            n_episodes: 1,
+            step_limit: None,
+            n_threads: 1,
+        }
+    }
+
+    pub fn with_episodes(self, n_episodes: usize) -> Self {
+        PolicySearch { n_episodes, ..self }
+    }
+
+    pub fn with_step_limit(self, step_limit: usize) -> Self {
+        PolicySearch {
+            step_limit: Some(step_limit),
+            ..self
+        }
+    }
+
+    pub fn with_threads(self, n_threads: usize) -> Self { PolicySearch { n_threads, ..self } }
+}
+
+impl<P: Parameterised + Clone, O> PolicySearch<P, O> {
+    fn fitness<D, F>(&self, make_domain: &F, candidate: &Array1<f64>) -> f64
+    where
+        D: Domain,
+        F: Fn() -> D,
+        P: for<'s> Policy<&'s State<D>, Action = Action<D>>,
+    {
+        let mut rng = thread_rng();
+        let mut policy = self.policy.clone();
+
+        policy
+            .weights_view_mut()
+            .iter_mut()
+            .zip(candidate.iter())
+            .for_each(|(w, c)| *w = *c);
+
+        let n = self.n_episodes.max(1);
+        let total: f64 = (0..n)
+            .map(|_| {
+                make_domain()
+                    .rollout(|s| policy.sample(&mut rng, s), self.step_limit)
+                    .total_reward()
+            })
+            .sum();
+
+        total / n as f64
+    }
+}
+
+impl<D, F, P, O> Handler<F> for PolicySearch<P, O>
+where
+    D: Domain,
+    F: Fn() -> D + Sync,
+    P: Parameterised + Clone + Sync + for<'s> Policy<&'s State<D>, Action = Action<D>>,
+    O: Strategy + Sync,
+{
+    type Response = Response;
+    type Error = ();
+
+    fn handle(&mut self, make_domain: F) -> Result<Response, ()> {
+        let candidates = self.strategy.ask(&mut thread_rng());
+        let n_threads = self.n_threads.max(1).min(candidates.len().max(1));
+
+        let fitness: Vec<f64> = if n_threads > 1 {
+            let chunk_size = candidates.len().div_ceil(n_threads);
+            let this = &*self;
+            let make_domain = &make_domain;
+
+            thread::scope(|scope| {
+                let handles: Vec<_> = candidates
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        scope.spawn(move || {
+                            chunk
+                                .iter()
+                                .map(|c| this.fitness(make_domain, c))
+                                .collect::<Vec<f64>>()
+                        })
+                    })
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .flat_map(|h| h.join().expect("Policy evaluation thread panicked."))
+                    .collect()
+            })
+        } else {
+            candidates
+                .iter()
+                .map(|c| self.fitness(&make_domain, c))
+                .collect()
+        };
+
+        self.strategy.tell(&candidates, &fitness);
+
+        let solution = self.strategy.solution();
+
+        self.policy
+            .weights_view_mut()
+            .iter_mut()
+            .zip(solution.iter())
+            .for_each(|(w, c)| *w = *c);
+
+        Ok(Response { fitness })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domains::bandits::Gaussian, fa::tabular::Table, policies::Greedy};
+    use ndarray::Array2;
+
+    /// Strategy that proposes the same population in every generation.
+    struct Fixed(Vec<Array1<f64>>);
+
+    impl Strategy for Fixed {
+        fn ask<R: Rng + ?Sized>(&mut self, _: &mut R) -> Vec<Array1<f64>> { self.0.clone() }
+
+        fn tell(&mut self, _: &[Array1<f64>], _: &[f64]) {}
+
+        fn solution(&self) -> Array1<f64> { self.0[0].clone() }
+    }
+
+    fn search(n_threads: usize) -> Vec<f64> {
+        let candidates = (0..5)
+            .map(|i| Array1::from_shape_fn(3, |j| if j == i % 3 { 1.0 } else { 0.0 }))
+            .collect();
+        let policy = Greedy::new(Table::dense(Array2::zeros((1, 3))));
+        let mut agent = PolicySearch::new(policy, Fixed(candidates))
+            .with_step_limit(4)
+            .with_threads(n_threads);
+
+        agent
+            .handle(|| Gaussian::homoscedastic(vec![0.0, 1.0, 2.0], 0.0))
+            .unwrap()
+            .fitness
+    }
+
+    #[test]
+    fn test_threads() {
+        let fitness = search(1);
+
+        assert_eq!(fitness, vec![0.0, 3.0, 6.0, 0.0, 3.0]);
+        assert_eq!(search(2), fitness);
+        assert_eq!(search(4), fitness);
+    }
+}
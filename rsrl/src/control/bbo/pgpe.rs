@@ -0,0 +1,160 @@
+use super::{standard_normal, Strategy};
+use ndarray::Array1;
+use rand::Rng;
+
+/// Policy gradients with parameter-based exploration.
+///
+/// Exploration takes place in parameter space rather than action space:
+/// weights are drawn from a diagonal Gaussian hyper-distribution,
+/// `N(mean, diag(stddev²))`, and each sample is used to run a deterministic
+/// policy, such as a `Point`, for whole episodes. Perturbations are sampled
+/// symmetrically, `mean ± ε`, so that the update of the mean depends only on
+/// the difference in return within each pair. The standard deviations are
+/// updated relative to a `baseline`, maintained as an exponential moving
+/// average of the returns with rate `baseline_rate`.
+///
+/// # References
+/// - Sehnke, F., Osendorfer, C., Rückstieß, T., Graves, A., Peters, J.,
+/// Schmidhuber, J. (2010). Parameter-exploring policy gradients. Neural
+/// Networks, 23(4), 551-559.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct PGPE {
+    pub mean: Array1<f64>,
+    pub stddev: Array1<f64>,
+
+    pub alpha_mean: f64,
+    pub alpha_stddev: f64,
+    pub n_pairs: usize,
+
+    pub baseline_rate: f64,
+    pub baseline: Option<f64>,
+}
+
+impl PGPE {
+    pub fn new(
+        mean: Array1<f64>,
+        stddev: f64,
+        alpha_mean: f64,
+        alpha_stddev: f64,
+        n_pairs: usize,
+    ) -> Self
+    {
+        let stddev = Array1::from_elem(mean.len(), stddev);
+
+        PGPE {
+            mean,
+            stddev,
+
+            alpha_mean,
+            alpha_stddev,
+            n_pairs,
+
+            baseline_rate: 0.1,
+            baseline: None,
+        }
+    }
+
+    pub fn with_baseline_rate(self, baseline_rate: f64) -> Self {
+        PGPE {
+            baseline_rate,
+            ..self
+        }
+    }
+}
+
+impl Strategy for PGPE {
+    fn ask<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<Array1<f64>> {
+        (0..self.n_pairs)
+            .flat_map(|_| {
+                let eps = standard_normal(rng, self.mean.len()) * &self.stddev;
+
+                vec![&self.mean + &eps, &self.mean - &eps]
+            })
+            .collect()
+    }
+
+    fn tell(&mut self, candidates: &[Array1<f64>], fitness: &[f64]) {
+        let n_pairs = candidates.len() / 2;
+
+        if n_pairs == 0 {
+            return;
+        }
+
+        let mean_fitness = fitness.iter().sum::<f64>() / fitness.len() as f64;
+        let baseline = self.baseline.unwrap_or(mean_fitness);
+
+        let mut grad_mean = Array1::zeros(self.mean.len());
+        let mut grad_stddev = Array1::zeros(self.mean.len());
+
+        for (pair, r) in candidates.chunks(2).zip(fitness.chunks(2)).take(n_pairs) {
+            let eps = &pair[0] - &self.mean;
+            let r_stddev = (r[0] + r[1]) / 2.0 - baseline;
+
+            grad_mean.scaled_add((r[0] - r[1]) / 2.0, &eps);
+            grad_stddev.scaled_add(
+                r_stddev,
+                &eps.iter()
+                    .zip(self.stddev.iter())
+                    .map(|(e, s)| (e * e - s * s) / s)
+                    .collect::<Array1<f64>>(),
+            );
+        }
+
+        let n = n_pairs as f64;
+
+        self.mean.scaled_add(self.alpha_mean / n, &grad_mean);
+        self.stddev.scaled_add(self.alpha_stddev / n, &grad_stddev);
+        self.stddev.mapv_inplace(|s| s.max(1e-6));
+
+        self.baseline = Some(baseline + self.baseline_rate * (mean_fitness - baseline));
+    }
+
+    fn solution(&self) -> Array1<f64> { self.mean.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+    #[test]
+    fn test_symmetric_sampling() {
+        let mut pgpe = PGPE::new(Array1::from(vec![1.0, -1.0]), 0.5, 0.1, 0.01, 5);
+        let candidates = pgpe.ask(&mut thread_rng());
+
+        assert_eq!(candidates.len(), 10);
+
+        for pair in candidates.chunks(2) {
+            let midpoint = (&pair[0] + &pair[1]) / 2.0;
+
+            assert!((midpoint[0] - 1.0).abs() < 1e-10);
+            assert!((midpoint[1] + 1.0).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut pgpe = PGPE::new(Array1::zeros(2), 1.0, 0.05, 0.01, 20);
+
+        for _ in 0..500 {
+            let candidates = pgpe.ask(&mut rng);
+            let fitness: Vec<f64> = candidates
+                .iter()
+                .map(|x| -(x[0] - 1.0).powi(2) - (x[1] + 2.0).powi(2))
+                .collect();
+
+            pgpe.tell(&candidates, &fitness);
+        }
+
+        let x = pgpe.solution();
+
+        assert!((x[0] - 1.0).abs() < 0.2);
+        assert!((x[1] + 2.0).abs() < 0.2);
+    }
+}
@@ -3,21 +3,28 @@ use crate::{
     domains::Transition,
     fa::StateActionUpdate,
     policies::Policy,
+    schedules::Schedule,
     Function,
     Handler,
 };
 
 /// Continuous Actor-Critic Learning Automaton
-pub struct CACLA<C, P> {
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct CACLA<C, P, A = f64> {
     pub critic: C,
     pub policy: P,
 
-    pub alpha: f64,
+    pub alpha: A,
     pub gamma: f64,
 }
 
-impl<C, P> CACLA<C, P> {
-    pub fn new(critic: C, policy: P, alpha: f64, gamma: f64) -> Self {
+impl<C, P, A> CACLA<C, P, A> {
+    pub fn new(critic: C, policy: P, alpha: A, gamma: f64) -> Self {
         CACLA {
             critic,
             policy,
@@ -28,9 +35,10 @@ impl<C, P> CACLA<C, P> {
     }
 }
 
-impl<'m, S, C, P> Handler<&'m Transition<S, P::Action>> for CACLA<C, P>
+impl<'m, S, C, P, A> Handler<&'m Transition<S, P::Action>> for CACLA<C, P, A>
 where
     C: Function<(&'m S,), Output = f64>,
+    A: Schedule,
     P: Policy<&'m S> + Handler<StateActionUpdate<&'m S, &'m <P as Policy<&'m S>>::Action, f64>>,
 
     P::Action: std::ops::Mul<f64, Output = f64>,
@@ -48,6 +56,14 @@ where
             t.reward + self.gamma * self.critic.evaluate((t.to.state(),))
         };
 
+        let alpha = self.alpha.value();
+
+        self.alpha.step();
+
+        if t.terminated() {
+            self.alpha.end_episode();
+        }
+
         if target > v {
             let mode = self.policy.mode(s);
 
@@ -55,7 +71,7 @@ where
                 .handle(StateActionUpdate {
                     state: s,
                     action: &t.action,
-                    error: (&t.action - mode) * self.alpha,
+                    error: (&t.action - mode) * alpha,
                 })
                 .map(|r| Some(r))
         } else {
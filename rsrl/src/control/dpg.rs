@@ -0,0 +1,322 @@
+//! Deterministic policy gradient methods.
+use crate::{
+    domains::Transition,
+    params::{Buffer, Parameterised},
+    policies::Point,
+    Differentiable,
+    Handler,
+};
+use ndarray::{Array1, Ix1};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Response {
+    pub td_error: f64,
+
+    /// Estimated advantage of the action taken, `A(s, a)`.
+    pub advantage: f64,
+}
+
+/// Return the policy gradient, `∇μ(s)`, and the compatible features,
+/// `φ(s, a) = ∇μ(s) (a - μ(s))`, for a scalar-valued `Point` policy.
+fn compatible_features<'m, S, F>(
+    policy: &Point<F>,
+    s: &'m S,
+    a: f64,
+) -> (Array1<f64>, Array1<f64>)
+where
+    F: Differentiable<(&'m S,), Output = f64>,
+    F::Jacobian: Buffer<Dim = Ix1>,
+{
+    let grad_mu = policy.0.grad((s,)).into_dense();
+    let phi = &grad_mu * (a - policy.0.evaluate((s,)));
+
+    (grad_mu, phi)
+}
+
+/// Compatible off-policy deterministic actor-critic with a Q-learning critic.
+///
+/// The critic approximates `Q(s, a) = φ(s, a)ᵀw + V(s)`, where `φ(s, a)` are
+/// the features compatible with the `Point` policy, `μ`, and `V` is the
+/// state-value function, `critic`, which should be linear in its weights. The
+/// advantage weights, `w`, double as the natural policy gradient, such that
+/// the actor follows `∇μ(s) ∇μ(s)ᵀw`. Since no importance sampling is
+/// required, actions may be drawn from any behaviour policy, e.g. `Noisy`.
+///
+/// # References
+/// - Silver, D., Lever, G., Heess, N., Degris, T., Wierstra, D., Riedmiller, M.
+/// (2014). Deterministic policy gradient algorithms. In Proceedings of ICML,
+/// pp. 387-395.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct COPDACQ<C, F> {
+    pub critic: C,
+    #[weights]
+    pub policy: Point<F>,
+
+    pub alpha_theta: f64,
+    pub alpha_w: f64,
+    pub alpha_v: f64,
+    pub gamma: f64,
+
+    w: Array1<f64>,
+}
+
+impl<C, F> COPDACQ<C, F> {
+    pub fn new(
+        critic: C,
+        policy: Point<F>,
+        alpha_theta: f64,
+        alpha_w: f64,
+        alpha_v: f64,
+        gamma: f64,
+    ) -> Self
+    {
+        COPDACQ {
+            critic,
+            policy,
+
+            alpha_theta,
+            alpha_w,
+            alpha_v,
+            gamma,
+
+            w: Array1::zeros(0),
+        }
+    }
+}
+
+impl<'m, S, C, F> Handler<&'m Transition<S, f64>> for COPDACQ<C, F>
+where
+    C: Differentiable<(&'m S,), Output = f64>,
+    F: Differentiable<(&'m S,), Output = f64>,
+
+    C::Jacobian: Buffer<Dim = Ix1>,
+    F::Jacobian: Buffer<Dim = Ix1>,
+{
+    type Response = Response;
+    type Error = ();
+
+    fn handle(&mut self, t: &'m Transition<S, f64>) -> Result<Response, ()> {
+        let s = t.from.state();
+        let (grad_mu, phi) = compatible_features(&self.policy, s, t.action);
+
+        if self.w.len() != phi.len() {
+            self.w = Array1::zeros(phi.len());
+        }
+
+        let advantage = phi.dot(&self.w);
+        let qsa = advantage + self.critic.evaluate((s,));
+        let td_error = if t.terminated() {
+            t.reward - qsa
+        } else {
+            t.reward + self.gamma * self.critic.evaluate((t.to.state(),)) - qsa
+        };
+
+        // Actor:
+        let step = self.alpha_theta * grad_mu.dot(&self.w);
+
+        self.policy.weights_view_mut().column_mut(0).scaled_add(step, &grad_mu);
+
+        // Critic:
+        self.w.scaled_add(self.alpha_w * td_error, &phi);
+        self.critic.grad((s,)).scaled_addto(
+            self.alpha_v * td_error,
+            &mut self.critic.weights_view_mut().column_mut(0),
+        );
+
+        Ok(Response {
+            td_error,
+            advantage,
+        })
+    }
+}
+
+/// Compatible off-policy deterministic actor-critic with a gradient Q-learning
+/// critic.
+///
+/// Identical to `COPDACQ`, except that the critic is learned with the
+/// gradient temporal-difference method of Greedy-GQ, which remains stable
+/// under off-policy sampling with linear function approximation. The
+/// secondary weights are updated with step size `alpha_u`, which defaults to a
+/// tenth of `alpha_w`.
+///
+/// # References
+/// - Silver, D., Lever, G., Heess, N., Degris, T., Wierstra, D., Riedmiller, M.
+/// (2014). Deterministic policy gradient algorithms. In Proceedings of ICML,
+/// pp. 387-395.
+/// - Maei, H. R., Szepesvári, C., Bhatnagar, S., Sutton, R. S. (2010). Toward
+/// off-policy learning control with function approximation. In Proceedings of
+/// ICML, pp. 719-726.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct COPDACGQ<C, F> {
+    pub critic: C,
+    #[weights]
+    pub policy: Point<F>,
+
+    pub alpha_theta: f64,
+    pub alpha_w: f64,
+    pub alpha_v: f64,
+    pub alpha_u: f64,
+    pub gamma: f64,
+
+    w: Array1<f64>,
+    u_w: Array1<f64>,
+    u_v: Array1<f64>,
+}
+
+impl<C, F> COPDACGQ<C, F> {
+    pub fn new(
+        critic: C,
+        policy: Point<F>,
+        alpha_theta: f64,
+        alpha_w: f64,
+        alpha_v: f64,
+        gamma: f64,
+    ) -> Self
+    {
+        COPDACGQ {
+            critic,
+            policy,
+
+            alpha_theta,
+            alpha_w,
+            alpha_v,
+            alpha_u: alpha_w / 10.0,
+            gamma,
+
+            w: Array1::zeros(0),
+            u_w: Array1::zeros(0),
+            u_v: Array1::zeros(0),
+        }
+    }
+}
+
+impl<'m, S, C, F> Handler<&'m Transition<S, f64>> for COPDACGQ<C, F>
+where
+    C: Differentiable<(&'m S,), Output = f64>,
+    F: Differentiable<(&'m S,), Output = f64>,
+
+    C::Jacobian: Buffer<Dim = Ix1>,
+    F::Jacobian: Buffer<Dim = Ix1>,
+{
+    type Response = Response;
+    type Error = ();
+
+    fn handle(&mut self, t: &'m Transition<S, f64>) -> Result<Response, ()> {
+        let s = t.from.state();
+        let (grad_mu, phi) = compatible_features(&self.policy, s, t.action);
+
+        let x = self.critic.grad((s,)).into_dense();
+
+        if self.w.len() != phi.len() {
+            self.w = Array1::zeros(phi.len());
+            self.u_w = Array1::zeros(phi.len());
+        }
+
+        if self.u_v.len() != x.len() {
+            self.u_v = Array1::zeros(x.len());
+        }
+
+        let advantage = phi.dot(&self.w);
+        let qsa = advantage + self.critic.evaluate((s,));
+        let (td_error, nx) = if t.terminated() {
+            (t.reward - qsa, Array1::zeros(x.len()))
+        } else {
+            let ns = t.to.state();
+
+            (
+                t.reward + self.gamma * self.critic.evaluate((ns,)) - qsa,
+                self.critic.grad((ns,)).into_dense(),
+            )
+        };
+
+        let phi_u = phi.dot(&self.u_w) + x.dot(&self.u_v);
+
+        // Actor:
+        let step = self.alpha_theta * grad_mu.dot(&self.w);
+
+        self.policy.weights_view_mut().column_mut(0).scaled_add(step, &grad_mu);
+
+        // Critic; the compatible features vanish at a' = μ(s'), so only the
+        // state-value weights receive a gradient correction.
+        self.w.scaled_add(self.alpha_w * td_error, &phi);
+
+        {
+            let mut v = self.critic.weights_view_mut();
+            let mut v = v.column_mut(0);
+
+            v.scaled_add(self.alpha_v * td_error, &x);
+            v.scaled_add(-self.alpha_v * self.gamma * phi_u, &nx);
+        }
+
+        self.u_w.scaled_add(self.alpha_u * (td_error - phi_u), &phi);
+        self.u_v.scaled_add(self.alpha_u * (td_error - phi_u), &x);
+
+        Ok(Response {
+            td_error,
+            advantage,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domains::Observation, fa::tabular::Table, Function};
+
+    // Single-step task with reward -(a - 2)², explored with actions μ ± 0.5.
+    fn transition(mu: f64, i: usize) -> Transition<usize, f64> {
+        let a = if i.is_multiple_of(2) { mu + 0.5 } else { mu - 0.5 };
+
+        Transition {
+            from: Observation::Full(0),
+            action: a,
+            reward: -(a - 2.0) * (a - 2.0),
+            to: Observation::Terminal(0),
+        }
+    }
+
+    #[test]
+    fn test_copdac_q_quadratic() {
+        let critic = Table::dense(Array1::zeros(1));
+        let policy = Point::new(Table::dense(Array1::zeros(1)));
+        let mut agent = COPDACQ::new(critic, policy, 0.1, 0.5, 0.5, 0.9);
+
+        for i in 0..500 {
+            let t = transition(agent.policy.0.evaluate((&0,)), i);
+
+            agent.handle(&t).unwrap();
+        }
+
+        assert!((agent.policy.0.evaluate((&0,)) - 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_copdac_gq_quadratic() {
+        let critic = Table::dense(Array1::zeros(1));
+        let policy = Point::new(Table::dense(Array1::zeros(1)));
+        let mut agent = COPDACGQ::new(critic, policy, 0.1, 0.5, 0.5, 0.9);
+
+        for i in 0..500 {
+            let t = transition(agent.policy.0.evaluate((&0,)), i);
+
+            agent.handle(&t).unwrap();
+        }
+
+        assert!((agent.policy.0.evaluate((&0,)) - 2.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,213 @@
+//! Least-squares policy iteration.
+use crate::{
+    domains::Batch,
+    fa::linear::{basis::Basis, Features, LinearBasis},
+    params::Parameterised,
+    prediction::lstd::lstdq,
+    Enumerable,
+    Function,
+    Handler,
+};
+use ndarray::{Array1, Array2};
+use std::ops::Index;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Response {
+    pub n_iters: usize,
+    pub converged: bool,
+}
+
+/// Least-squares policy iteration.
+///
+/// Alternates between LSTDQ evaluation of the greedy policy over a fixed
+/// batch of transitions and greedy improvement, until the greedy policy
+/// stops changing on the batch or the weights change by less than `tol`.
+/// Features are taken from the basis of the linear `q_func`, into whose
+/// weights the solution is written; `q_func` may therefore be `Shared` with a
+/// policy, such as `Greedy`, in the usual way.
+///
+/// # References
+/// - Lagoudakis, M. G., & Parr, R. (2003). Least-squares policy iteration.
+/// Journal of Machine Learning Research, 4, 1107-1149.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct LSPI<Q> {
+    #[weights]
+    pub q_func: Q,
+
+    pub gamma: f64,
+    pub tol: f64,
+    pub max_iter: usize,
+}
+
+impl<Q> LSPI<Q> {
+    pub fn new(q_func: Q, gamma: f64) -> Self {
+        LSPI {
+            q_func,
+
+            gamma,
+            tol: 1e-5,
+            max_iter: 20,
+        }
+    }
+}
+
+impl<'m, S, Q> Handler<&'m Batch<S, usize>> for LSPI<Q>
+where
+    Q: Enumerable<(&'m S,)> + LinearBasis + Parameterised,
+    Q::Basis: Basis<&'m S, Value = Features>,
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Response;
+    type Error = crate::fa::linear::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, usize>) -> Result<Response, Self::Error> {
+        let (n_features, n_actions) = self.q_func.weights_dim();
+        let n_params = n_features * n_actions;
+
+        let greedy_actions = |q: &Q| -> Vec<usize> {
+            batch
+                .iter()
+                .filter(|t| !t.terminated())
+                .map(|t| q.find_max((t.to.state(),)).0)
+                .collect()
+        };
+
+        let mut actions = greedy_actions(&self.q_func);
+
+        for i in 0..self.max_iter {
+            let mut a = Array2::eye(n_params) * 1e-6;
+            let mut b = Array1::zeros(n_params);
+            let basis = self.q_func.basis();
+
+            for (t, &na) in batch.iter().filter(|t| !t.terminated()).zip(actions.iter()) {
+                let mut probs = vec![0.0; n_actions];
+                probs[na] = 1.0;
+
+                accumulate_transition(basis, &mut a, &mut b, self.gamma, t, Some(&probs))?;
+            }
+
+            for t in batch.iter().filter(|t| t.terminated()) {
+                accumulate_transition(basis, &mut a, &mut b, self.gamma, t, None)?;
+            }
+
+            let theta = match lstdq::solve(&a, &b, n_actions) {
+                Some(theta) => theta,
+                None => break,
+            };
+
+            let delta = (&theta - &self.q_func.weights_view())
+                .fold(0.0, |acc, x| acc + x * x)
+                .sqrt();
+
+            self.q_func.weights_view_mut().assign(&theta);
+
+            let new_actions = greedy_actions(&self.q_func);
+
+            if delta < self.tol || new_actions == actions {
+                return Ok(Response {
+                    n_iters: i + 1,
+                    converged: true,
+                });
+            }
+
+            actions = new_actions;
+        }
+
+        Ok(Response {
+            n_iters: self.max_iter,
+            converged: false,
+        })
+    }
+}
+
+fn accumulate_transition<'m, S, B>(
+    basis: &B,
+    a: &mut Array2<f64>,
+    b: &mut Array1<f64>,
+    gamma: f64,
+    t: &'m crate::domains::Transition<S, usize>,
+    probs: Option<&[f64]>,
+) -> Result<(), crate::fa::linear::Error>
+where B: Basis<&'m S, Value = Features> {
+    let phi_s = basis.project(t.from.state())?.into_dense();
+    let next = match probs {
+        Some(probs) => Some((basis.project(t.to.state())?.into_dense(), probs)),
+        None => None,
+    };
+
+    lstdq::accumulate(a, b, gamma, phi_s, t.action, t.reward, next);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::{Observation, Transition},
+        fa::linear::{basis::Closure, optim::SGD, LFA},
+        make_shared,
+        policies::{Greedy, Policy},
+    };
+
+    #[test]
+    fn test_chain() {
+        // Four states in a line; moving left (action 0) from the first state
+        // terminates with unit reward, while moving right stays in bounds.
+        let batch: Batch<usize, usize> = (0..4)
+            .flat_map(|s: usize| {
+                vec![
+                    Transition {
+                        from: Observation::Full(s),
+                        action: 0,
+                        reward: if s == 0 { 1.0 } else { 0.0 },
+                        to: if s == 0 {
+                            Observation::Terminal(s)
+                        } else {
+                            Observation::Full(s - 1)
+                        },
+                    },
+                    Transition {
+                        from: Observation::Full(s),
+                        action: 1,
+                        reward: 0.0,
+                        to: Observation::Full((s + 1).min(3)),
+                    },
+                ]
+            })
+            .collect();
+
+        let basis = Closure::new(4, |s: &usize| {
+            let mut phi = Array1::zeros(4);
+            phi[*s] = 1.0;
+
+            Ok(Features::Dense(phi))
+        });
+        let q_func = make_shared(LFA::vector(basis, SGD(1.0), 2));
+        let policy = Greedy::new(q_func.clone());
+        let mut lspi = LSPI::new(q_func, 0.9);
+
+        // Ties are broken towards the last action, so the initial greedy
+        // policy moves right everywhere and must be improved upon.
+        let res = lspi.handle(&batch).unwrap();
+
+        assert!(res.converged);
+        assert!(res.n_iters > 1);
+
+        for s in 0..4 {
+            assert_eq!(policy.mode(&s), 0);
+            assert!((lspi.q_func.weights[(s, 0)] - 0.9f64.powi(s as i32)).abs() < 1e-4);
+        }
+    }
+}
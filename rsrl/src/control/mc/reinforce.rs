@@ -1,4 +1,10 @@
-use crate::{domains::Batch, fa::StateActionUpdate, policies::Policy, Handler};
+use crate::{
+    domains::Batch,
+    fa::StateActionUpdate,
+    policies::Policy,
+    schedules::Schedule,
+    Handler,
+};
 
 #[derive(Clone, Debug, Parameterised)]
 #[cfg_attr(
@@ -6,42 +12,100 @@ use crate::{domains::Batch, fa::StateActionUpdate, policies::Policy, Handler};
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate")
 )]
-pub struct REINFORCE<P> {
+pub struct REINFORCE<P, A = f64> {
     #[weights]
     pub policy: P,
 
-    pub alpha: f64,
+    pub alpha: A,
     pub gamma: f64,
+    pub entropy: f64,
 }
 
-impl<P> REINFORCE<P> {
-    pub fn new(policy: P, alpha: f64, gamma: f64) -> Self {
+impl<P, A> REINFORCE<P, A> {
+    pub fn new(policy: P, alpha: A, gamma: f64) -> Self {
         REINFORCE {
             policy,
 
             alpha,
             gamma,
+            entropy: 0.0,
         }
     }
+
+    /// Add a sampled entropy bonus, `-entropy * ln π(a | s)`, to the returns.
+    pub fn with_entropy(self, entropy: f64) -> Self { REINFORCE { entropy, ..self } }
 }
 
-impl<'m, S, P> Handler<&'m Batch<S, P::Action>> for REINFORCE<P>
-where P: Policy<S> + Handler<StateActionUpdate<&'m S, &'m <P as Policy<S>>::Action>>
+impl<'m, S, P, A> Handler<&'m Batch<S, P::Action>> for REINFORCE<P, A>
+where
+    P: Policy<&'m S> + Handler<StateActionUpdate<&'m S, &'m <P as Policy<&'m S>>::Action>>,
+    A: Schedule,
 {
     type Response = Vec<P::Response>;
     type Error = P::Error;
 
     fn handle(&mut self, batch: &'m Batch<S, P::Action>) -> Result<Self::Response, Self::Error> {
         let mut ret = 0.0;
+        let alpha = self.alpha.value();
+
+        let responses = batch.iter().map(|t| {
+            let s = t.from.state();
+            let bonus = if self.entropy > 0.0 {
+                -self.entropy * self.policy.evaluate((s, &t.action)).ln()
+            } else {
+                0.0
+            };
 
-        batch.iter().map(|t| {
             ret = t.reward + self.gamma * ret;
 
             self.policy.handle(StateActionUpdate {
-                state: t.from.state(),
+                state: s,
                 action: &t.action,
-                error: self.alpha * ret,
+                error: alpha * (ret + bonus),
             })
-        }).collect()
+        }).collect();
+
+        for _ in batch {
+            self.alpha.step();
+        }
+
+        self.alpha.end_episode();
+
+        responses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::{Observation, Transition},
+        fa::tabular::Table,
+        params::Parameterised,
+        policies::Softmax,
+    };
+    use ndarray::Array2;
+
+    #[test]
+    fn test_softmax_entropy() {
+        let policy = Softmax::standard(Table::dense(Array2::zeros((2, 3))));
+        let mut agent = REINFORCE::new(policy, 0.1, 0.9).with_entropy(0.01);
+
+        let batch = vec![Transition {
+            from: Observation::Full(0usize),
+            action: 2usize,
+            reward: 1.0,
+            to: Observation::Terminal(1),
+        }];
+
+        assert!(agent.handle(&batch).is_ok());
+
+        // π(2 | 0) = 1/3, so the bonus is 0.01 ln 3 and ∇ ln π = e_2 - 1/3.
+        let w = agent.policy.weights_view();
+        let step = 0.1 * (1.0 + 0.01 * 3.0f64.ln());
+
+        assert!((w[(0, 2)] - step * 2.0 / 3.0).abs() < 1e-10);
+        assert!((w[(0, 1)] + step / 3.0).abs() < 1e-10);
+        assert!(w.iter().all(|w| w.is_finite()));
     }
 }
@@ -2,6 +2,9 @@
 // Critic-only:
 pub mod td;
 
+// Bandits:
+pub mod bandits;
+
 // Actor-only:
 pub mod mc;
 
@@ -9,6 +12,19 @@ pub mod mc;
 pub mod ac;
 pub mod nac;
 pub mod cacla;
+pub mod ppo;
+pub mod trpo;
+pub mod offpac;
+pub mod dpg;
+
+// Derivative-free:
+pub mod bbo;
+
+// Batch:
+pub mod fitted_q_iteration;
+pub mod lspi;
+
+pub use self::{fitted_q_iteration::FittedQIteration, lspi::LSPI};
 
 // TODO
 // Proximal gradient-descent methods:
@@ -1,5 +1,18 @@
 //! Natural actor-critic algorithms.
-use crate::{fa::ScaledGradientUpdate, params::*, Handler};
+use crate::{
+    domains::Trajectories,
+    fa::{
+        linear::basis::{Basis, CompatibleBasis},
+        ScaledGradientUpdate,
+    },
+    params::*,
+    policies::DifferentiablePolicy,
+    schedules::Schedule,
+    utils::pinv,
+    Handler,
+};
+use ndarray::{Array1, Array2};
+use ndarray_linalg::Solve;
 
 #[derive(Clone, Debug)]
 #[cfg_attr(
@@ -12,21 +25,23 @@ pub struct Response {
 }
 
 /// Natural actor-critic.
+///
+/// Each call to `handle` counts as a single step of the `alpha` schedule.
 #[derive(Clone, Debug)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate")
 )]
-pub struct NAC<C, P> {
+pub struct NAC<C, P, A = f64> {
     pub critic: C,
     pub policy: P,
 
-    pub alpha: f64,
+    pub alpha: A,
 }
 
-impl<C, P> NAC<C, P> {
-    pub fn new(critic: C, policy: P, alpha: f64) -> Self {
+impl<C, P, A> NAC<C, P, A> {
+    pub fn new(critic: C, policy: P, alpha: A) -> Self {
         NAC {
             critic,
             policy,
@@ -36,9 +51,10 @@ impl<C, P> NAC<C, P> {
     }
 }
 
-impl<M, C, P> Handler<M> for NAC<C, P>
+impl<M, C, P, A> Handler<M> for NAC<C, P, A>
 where
     C: Parameterised,
+    A: Schedule,
     P: Parameterised + for<'m> Handler<ScaledGradientUpdate<WeightsView<'m>>>,
 {
     type Response = Response;
@@ -52,9 +68,173 @@ where
         let grad = cw.slice(s![0..n_features, ..]).into_shape(pw_dim).unwrap();
         let norm = grad.fold(0.0, |acc, g| acc + g * g).sqrt().max(1e-3);
 
+        let alpha = self.alpha.value();
+
+        self.alpha.step();
+
         self.policy.handle(ScaledGradientUpdate {
-            alpha: self.alpha / norm,
+            alpha: alpha / norm,
             jacobian: grad,
         }).map(|_| Response { norm, }).map_err(|_| ())
     }
 }
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct EpisodicResponse {
+    /// Norm of the estimated natural gradient.
+    pub norm: f64,
+
+    /// Estimated baseline, i.e. the expected discounted return.
+    pub baseline: f64,
+}
+
+/// Episodic natural actor-critic.
+///
+/// The discounted return of each episode is regressed onto the discounted sum
+/// of its compatible features, `CompatibleBasis`, plus a constant baseline
+/// term. The resulting weights on the compatible features give the natural
+/// gradient, which is applied as a single `ScaledGradientUpdate`. Features are
+/// computed from a snapshot of the policy taken at the start of each call to
+/// `handle`, and each call counts as a single step of the `alpha` schedule.
+///
+/// # References
+/// - Peters, J., & Schaal, S. (2008). Natural actor-critic. Neurocomputing,
+/// 71(7-9), 1180-1190.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct ENAC<P, A = f64> {
+    #[weights]
+    pub policy: P,
+
+    pub alpha: A,
+    pub gamma: f64,
+}
+
+impl<P, A> ENAC<P, A> {
+    pub fn new(policy: P, alpha: A, gamma: f64) -> Self {
+        ENAC {
+            policy,
+
+            alpha,
+            gamma,
+        }
+    }
+}
+
+impl<'m, S, P, A> Handler<&'m Trajectories<S, P::Action>> for ENAC<P, A>
+where
+    A: Schedule,
+    P: DifferentiablePolicy<&'m S> + Clone + Handler<ScaledGradientUpdate<Array2<f64>>>,
+    P::Action: Clone,
+{
+    type Response = EpisodicResponse;
+    type Error = ();
+
+    fn handle(
+        &mut self,
+        trajectories: &'m Trajectories<S, P::Action>,
+    ) -> Result<Self::Response, Self::Error>
+    {
+        let basis = CompatibleBasis(self.policy.clone());
+
+        let n_params = self.policy.n_weights();
+        let dim = n_params + 1;
+
+        let mut a = Array2::eye(dim) * 1e-6;
+        let mut b = Array1::zeros(dim);
+
+        for traj in trajectories {
+            let mut psi = Array1::zeros(dim);
+            let mut ret = 0.0;
+            let mut discount = 1.0;
+
+            psi[n_params] = 1.0;
+
+            for t in traj.iter() {
+                let phi = basis.project((*t.from.state(), t.action.clone())).map_err(|_| ())?;
+
+                phi.scaled_addto(discount, &mut psi.slice_mut(s![0..n_params]));
+
+                ret += discount * t.reward;
+                discount *= self.gamma;
+            }
+
+            for i in 0..dim {
+                for j in 0..dim {
+                    a[(i, j)] += psi[i] * psi[j];
+                }
+            }
+
+            b.scaled_add(ret, &psi);
+        }
+
+        let w = a
+            .solve(&b)
+            .or_else(|_| pinv(&a).map(|ainv| ainv.dot(&b)))
+            .map_err(|_| ())?;
+
+        let grad = Array2::from_shape_vec(
+            self.policy.weights_dim(),
+            w.slice(s![0..n_params]).to_vec(),
+        ).map_err(|_| ())?;
+        let norm = grad.fold(0.0, |acc, g| acc + g * g).sqrt();
+
+        let alpha = self.alpha.value();
+
+        self.alpha.step();
+
+        self.policy.handle(ScaledGradientUpdate {
+            alpha,
+            jacobian: grad,
+        }).map(|_| EpisodicResponse {
+            norm,
+            baseline: w[n_params],
+        }).map_err(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::{Observation, Trajectory},
+        fa::tabular::Table,
+        policies::Softmax,
+    };
+
+    #[test]
+    fn test_enac_bandit() {
+        let policy = Softmax::standard(Table::dense(Array2::zeros((1, 2))));
+        let mut agent = ENAC::new(policy, 0.1, 1.0);
+
+        let trajectories: Trajectories<usize, usize> = vec![
+            Trajectory {
+                start: Observation::Full(0),
+                steps: vec![(Observation::Terminal(0), 0, 1.0)],
+            },
+            Trajectory {
+                start: Observation::Full(0),
+                steps: vec![(Observation::Terminal(0), 1, 0.0)],
+            },
+        ];
+        let res = agent.handle(&trajectories).unwrap();
+
+        // With π uniform, ψ(a) = ±ψ for ψ = (1/2, -1/2), so F = ψψᵀ and
+        // ∇J = ψ/2, giving a natural gradient F⁺∇J = ψ and a baseline J = 1/2.
+        let w = agent.policy.weights_view();
+
+        assert!((res.baseline - 0.5).abs() < 1e-4);
+        assert!((res.norm - 0.5f64.sqrt()).abs() < 1e-4);
+        assert!((w[(0, 0)] - 0.05).abs() < 1e-5);
+        assert!((w[(0, 1)] + 0.05).abs() < 1e-5);
+    }
+}
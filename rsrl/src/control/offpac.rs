@@ -0,0 +1,200 @@
+//! Off-policy actor-critic.
+use crate::{
+    domains::Transition,
+    fa::ScaledGradientUpdate,
+    params::Buffer,
+    policies::{DifferentiablePolicy, Policy},
+    Differentiable,
+    Handler,
+};
+use ndarray::{Array1, Array2};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Response {
+    pub td_error: f64,
+
+    /// Importance sampling ratio, `π(a | s) / b(a | s)`.
+    pub rho: f64,
+}
+
+/// Off-policy actor-critic (Off-PAC).
+///
+/// The `critic` is a state-value function learned with GTD(λ), which reduces
+/// to TDC when `lambda = 0`; its gradient is used as the feature vector, so the
+/// critic should be linear in its weights. The actor is updated along
+/// importance-weighted eligibility traces of `grad_log`, allowing `policy` to
+/// be learned from actions sampled by a separate `behaviour` policy. The
+/// secondary step size, `alpha_w`, defaults to a tenth of `alpha_v`.
+///
+/// # References
+/// - Degris, T., White, M., Sutton, R. S. (2012). Off-policy actor-critic. In
+/// Proceedings of ICML, pp. 179-186.
+/// - Maei, H. R. (2011). Gradient temporal-difference learning algorithms.
+/// Ph.D. thesis, University of Alberta.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct OffPAC<C, P, B> {
+    pub critic: C,
+    #[weights]
+    pub policy: P,
+    pub behaviour: B,
+
+    pub alpha_u: f64,
+    pub alpha_v: f64,
+    pub alpha_w: f64,
+    pub gamma: f64,
+    pub lambda: f64,
+
+    w: Array1<f64>,
+    e_v: Array1<f64>,
+    e_u: Array2<f64>,
+}
+
+impl<C, P, B> OffPAC<C, P, B> {
+    pub fn new(
+        critic: C,
+        policy: P,
+        behaviour: B,
+        alpha_u: f64,
+        alpha_v: f64,
+        gamma: f64,
+        lambda: f64,
+    ) -> Self
+    {
+        OffPAC {
+            critic,
+            policy,
+            behaviour,
+
+            alpha_u,
+            alpha_v,
+            alpha_w: alpha_v / 10.0,
+            gamma,
+            lambda,
+
+            w: Array1::zeros(0),
+            e_v: Array1::zeros(0),
+            e_u: Array2::zeros((0, 0)),
+        }
+    }
+
+    /// Reset the eligibility traces, e.g. at the start of a new episode.
+    pub fn reset_traces(&mut self) {
+        self.e_v.fill(0.0);
+        self.e_u.fill(0.0);
+    }
+}
+
+impl<'m, S, C, P, B> Handler<&'m Transition<S, P::Action>> for OffPAC<C, P, B>
+where
+    C: Differentiable<(&'m S,), Output = f64>,
+    P: DifferentiablePolicy<&'m S> + Handler<ScaledGradientUpdate<Array2<f64>>>,
+    B: Policy<&'m S, Action = P::Action>,
+{
+    type Response = Response;
+    type Error = P::Error;
+
+    fn handle(&mut self, t: &'m Transition<S, P::Action>) -> Result<Response, Self::Error> {
+        let s = t.from.state();
+        let n_v = self.critic.n_weights();
+
+        if self.w.len() != n_v {
+            self.w = Array1::zeros(n_v);
+            self.e_v = Array1::zeros(n_v);
+        }
+
+        if self.e_u.dim() != self.policy.weights_dim() {
+            self.e_u = Array2::zeros(self.policy.weights_dim());
+        }
+
+        let rho = self.policy.evaluate((s, &t.action)) / self.behaviour.evaluate((s, &t.action));
+
+        let x: Array1<f64> = self.critic.grad((s,)).to_dense().iter().cloned().collect();
+        let v = self.critic.evaluate((s,));
+
+        let (td_error, nx) = if t.terminated() {
+            (t.reward - v, Array1::zeros(n_v))
+        } else {
+            let ns = t.to.state();
+            let nx: Array1<f64> = self.critic.grad((ns,)).to_dense().iter().cloned().collect();
+
+            (t.reward + self.gamma * self.critic.evaluate((ns,)) - v, nx)
+        };
+
+        // Critic: GTD(lambda).
+        let gl = self.gamma * self.lambda;
+
+        self.e_v = (&self.e_v * gl + &x) * rho;
+
+        let alpha_v = self.alpha_v;
+        let correction = self.gamma * (1.0 - self.lambda) * self.w.dot(&self.e_v);
+        let wx = self.w.dot(&x);
+
+        self.critic
+            .weights_view_mut()
+            .iter_mut()
+            .zip(self.e_v.iter().zip(nx.iter()))
+            .for_each(|(v, (e, nx))| *v += alpha_v * (td_error * e - correction * nx));
+
+        self.w.scaled_add(self.alpha_w * td_error, &self.e_v);
+        self.w.scaled_add(-self.alpha_w * wx, &x);
+
+        // Actor: importance-weighted policy gradient.
+        self.e_u = (&self.e_u * gl + &self.policy.grad_log((s, &t.action))) * rho;
+
+        self.policy.handle(ScaledGradientUpdate {
+            alpha: self.alpha_u * td_error,
+            jacobian: self.e_u.clone(),
+        })?;
+
+        if t.terminated() {
+            self.reset_traces();
+        }
+
+        Ok(Response { td_error, rho })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::Observation,
+        fa::tabular::Table,
+        params::Parameterised,
+        policies::{Random, Softmax},
+    };
+
+    #[test]
+    fn test_softmax_importance_ratio() {
+        // π(· | 0) = (1/5, 3/5, 1/5) against a uniform behaviour policy.
+        let mut prefs = Array2::zeros((2, 3));
+        prefs[(0, 1)] = 3.0f64.ln();
+
+        let policy = Softmax::standard(Table::dense(prefs));
+        let critic = Table::dense(Array1::zeros(2));
+        let mut agent = OffPAC::new(critic, policy, Random::new(3), 0.1, 0.1, 0.9, 0.0);
+
+        let t = Transition {
+            from: Observation::Full(0usize),
+            action: 1usize,
+            reward: 1.0,
+            to: Observation::Full(1),
+        };
+        let res = agent.handle(&t).unwrap();
+
+        assert!((res.rho - 1.8).abs() < 1e-10);
+        assert!((res.td_error - 1.0).abs() < 1e-10);
+        assert!(agent.policy.weights_view().iter().all(|w| w.is_finite()));
+        assert!(agent.policy.weights_view()[(0, 1)] > 3.0f64.ln());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{counts::Counts, domains::Transition, Handler};
+
+/// Count-based exploration bonus for temporal-difference agents.
+///
+/// Each `Transition` is recorded in `counts` and then forwarded to `agent`
+/// with its reward augmented by `beta / sqrt(N(s, a))`. Rarely visited
+/// state-action pairs therefore appear more valuable, driving the agent to
+/// explore them even when acting greedily. Since the augmented transition is
+/// constructed afresh, the states of the domain must implement `Clone`.
+///
+/// # References
+/// - Strehl, A. L., Littman, M. L. (2008). An analysis of model-based interval
+/// estimation for Markov decision processes. Journal of Computer and System
+/// Sciences, 74(8), 1309-1331.
+/// - Bellemare, M., et al. (2016). Unifying count-based exploration and
+/// intrinsic motivation. In Proceedings of NIPS, pp. 1471-1479.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct CountBonus<A, C> {
+    pub agent: A,
+    pub counts: C,
+
+    pub beta: f64,
+}
+
+impl<A, C> CountBonus<A, C> {
+    pub fn new(agent: A, counts: C, beta: f64) -> Self {
+        CountBonus {
+            agent,
+            counts,
+
+            beta,
+        }
+    }
+}
+
+impl<'m, S, A, C, R, E> Handler<&'m Transition<S, usize>> for CountBonus<A, C>
+where
+    S: Clone,
+    A: for<'a> Handler<&'a Transition<S, usize>, Response = R, Error = E>,
+    C: Counts<&'m S>,
+{
+    type Response = R;
+    type Error = E;
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<R, E> {
+        let s = t.from.state();
+
+        self.counts.increment(s, t.action);
+
+        let n = self.counts.count(s, t.action) as f64;
+        let mut t = t.clone();
+
+        t.reward += self.beta / n.sqrt();
+
+        self.agent.handle(&t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{counts::TabularCounts, domains::Observation};
+
+    /// Agent that responds with the reward it was given.
+    struct Rewards;
+
+    impl<'a> Handler<&'a Transition<usize, usize>> for Rewards {
+        type Response = f64;
+        type Error = ();
+
+        fn handle(&mut self, t: &'a Transition<usize, usize>) -> Result<f64, ()> { Ok(t.reward) }
+    }
+
+    fn transition(from: usize, action: usize) -> Transition<usize, usize> {
+        Transition {
+            from: Observation::Full(from),
+            action,
+            reward: 1.0,
+            to: Observation::Full(0),
+        }
+    }
+
+    #[test]
+    fn test_bonus() {
+        let mut agent = CountBonus::new(Rewards, TabularCounts::new(2, 2), 0.5);
+
+        assert_eq!(agent.handle(&transition(0, 0)), Ok(1.5));
+        assert_eq!(agent.handle(&transition(0, 0)), Ok(1.0 + 0.5 / 2.0f64.sqrt()));
+        assert_eq!(agent.handle(&transition(0, 0)), Ok(1.0 + 0.5 / 3.0f64.sqrt()));
+        assert_eq!(agent.handle(&transition(0, 1)), Ok(1.5));
+        assert_eq!(agent.handle(&transition(1, 0)), Ok(1.5));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    domains::Transition,
+    fa::StateActionUpdate,
+    policies::Policy,
+    Function,
+    Handler,
+    Parameterised,
+};
+use rand::thread_rng;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Response<R> {
+    pub td_error: f64,
+    pub rho: f64,
+    pub qfunc_response: R,
+}
+
+/// Differential semi-gradient SARSA for average-reward control.
+///
+/// The discounted return is replaced with the differential return, with
+/// `rho` tracking the average reward per step under the agent's policy using
+/// step size `beta`. The step size of the value update is left to `q_func`.
+///
+/// # References
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.), Section 10.3. MIT Press.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct DifferentialSARSA<Q, P> {
+    #[weights]
+    pub q_func: Q,
+    pub policy: P,
+
+    pub beta: f64,
+
+    pub rho: f64,
+}
+
+impl<Q, P> DifferentialSARSA<Q, P> {
+    pub fn new(q_func: Q, policy: P, beta: f64) -> Self {
+        DifferentialSARSA {
+            q_func,
+            policy,
+
+            beta,
+
+            rho: 0.0,
+        }
+    }
+}
+
+impl<'m, S, Q, P> Handler<&'m Transition<S, P::Action>> for DifferentialSARSA<Q, P>
+where
+    Q: Function<(&'m S, P::Action), Output = f64>
+        + for<'a> Function<(&'m S, &'a P::Action), Output = f64>
+        + Handler<StateActionUpdate<&'m S, &'m P::Action>>,
+    P: Policy<&'m S>,
+{
+    type Response = Response<Q::Response>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, t: &'m Transition<S, P::Action>) -> Result<Self::Response, Self::Error> {
+        let s = t.from.state();
+        let qsa = self.q_func.evaluate((s, &t.action));
+
+        let td_error = if t.terminated() {
+            t.reward - self.rho - qsa
+        } else {
+            let ns = t.to.state();
+            let na = self.policy.sample(&mut thread_rng(), ns);
+            let nqsna = self.q_func.evaluate((ns, na));
+
+            t.reward - self.rho + nqsna - qsa
+        };
+
+        self.rho += self.beta * td_error;
+
+        self.q_func.handle(StateActionUpdate {
+            state: s,
+            action: &t.action,
+            error: td_error,
+        }).map(|r| Response {
+            td_error,
+            rho: self.rho,
+            qfunc_response: r,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domains::Observation, fa::tabular::Table, policies::Random};
+    use ndarray::Array2;
+
+    #[test]
+    fn test_average_reward() {
+        let q_func = Table::dense(Array2::zeros((2, 1)));
+        let mut agent = DifferentialSARSA::new(q_func, Random::new(1), 0.1);
+
+        // Cycle between two states with rewards 1 and 0.
+        let transitions: Vec<Transition<usize, usize>> = (0..2)
+            .map(|s| Transition {
+                from: Observation::Full(s),
+                action: 0,
+                reward: (1 - s) as f64,
+                to: Observation::Full(1 - s),
+            })
+            .collect();
+
+        for i in 0..200 {
+            agent.handle(&transitions[i % 2]).unwrap();
+        }
+
+        assert!((agent.rho - 0.5).abs() < 1e-3);
+    }
+}
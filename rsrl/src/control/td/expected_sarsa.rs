@@ -1,16 +1,19 @@
 use crate::{
-    domains::Transition,
-    fa::StateActionUpdate,
+    domains::{Batch, Trajectory, Transition},
+    fa::{StateActionUpdate, TargetFunction},
+    params::Parameterised,
     policies::EnumerablePolicy,
     Enumerable,
     Function,
     Handler,
-    Parameterised,
 };
 use std::ops::Index;
 
 /// Action probability-weighted variant of SARSA (aka "summation Q-learning").
 ///
+/// Bootstrap targets are computed from `target`, when present, in place of the
+/// online `q_func`.
+///
 /// # References
 /// - Rummery, G. A. (1995). Problem Solving with Reinforcement Learning. Ph.D
 /// thesis, Cambridge University.
@@ -19,6 +22,11 @@ use std::ops::Index;
 /// IEEE Symposium on Adaptive Dynamic Programming and Reinforcement Learning,
 /// pp. 177–184.
 #[derive(Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct ExpectedSARSA<Q, P> {
     #[weights]
     pub q_func: Q,
@@ -26,11 +34,73 @@ pub struct ExpectedSARSA<Q, P> {
 
     pub alpha: f64,
     pub gamma: f64,
+
+    pub target: Option<TargetFunction<Q>>,
+}
+
+impl<Q, P> ExpectedSARSA<Q, P> {
+    pub fn new(q_func: Q, policy: P, alpha: f64, gamma: f64) -> Self {
+        ExpectedSARSA {
+            q_func,
+            policy,
+
+            alpha,
+            gamma,
+
+            target: None,
+        }
+    }
+
+    /// Compute bootstrap targets from a separate target function.
+    pub fn with_target(self, target: TargetFunction<Q>) -> Self {
+        ExpectedSARSA {
+            target: Some(target),
+            ..self
+        }
+    }
+}
+
+impl<Q: Parameterised, P> ExpectedSARSA<Q, P> {
+    fn td_error<'m, S>(&self, t: &Transition<&'m S, &'m usize>) -> f64
+    where
+        Q: Enumerable<(&'m S,)>,
+        P: EnumerablePolicy<&'m S>,
+
+        <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+        <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+
+        <P as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+        <<P as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        let qsa = self.q_func.evaluate_index((*t.from.state(),), *t.action);
+
+        if t.terminated() {
+            t.reward - qsa
+        } else {
+            let ns = *t.to.state();
+            let nqs = match self.target {
+                Some(ref target) => target.evaluate((ns,)),
+                None => self.q_func.evaluate((ns,)),
+            };
+            let exp_nv = nqs
+                .into_iter()
+                .zip(self.policy.evaluate((ns,)).into_iter())
+                .fold(0.0, |acc, (q, p)| acc + q * p);
+
+            t.reward + self.gamma * exp_nv - qsa
+        }
+    }
+
+    fn sync_target(&mut self) {
+        if let Some(ref mut target) = self.target {
+            target.update(&self.q_func);
+        }
+    }
 }
 
 impl<'m, S, Q, P> Handler<&'m Transition<S, usize>> for ExpectedSARSA<Q, P>
 where
-    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>>,
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
     P: EnumerablePolicy<&'m S>,
 
     <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
@@ -43,25 +113,132 @@ where
     type Error = Q::Error;
 
     fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<Self::Response, Self::Error> {
-        let s = t.from.state();
-        let qsa = self.q_func.evaluate_index((s,), t.action);
-        let residual = if t.terminated() {
-            t.reward - qsa
-        } else {
-            let ns = t.to.state();
-            let exp_nv = self.q_func
-                .evaluate((ns,))
-                .into_iter()
-                .zip(self.policy.evaluate((ns,)).into_iter())
-                .fold(0.0, |acc, (q, p)| acc + q * p);
+        let residual = self.td_error(&t.borrowed());
+        let response = self.q_func.handle(StateActionUpdate {
+            state: t.from.state(),
+            action: t.action,
+            error: self.alpha * residual,
+        })?;
 
-            t.reward + self.gamma * exp_nv - qsa
+        self.sync_target();
+
+        Ok(response)
+    }
+}
+
+impl<'m, S, Q, P> Handler<&'m Batch<S, usize>> for ExpectedSARSA<Q, P>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
+    P: EnumerablePolicy<&'m S>,
+
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+
+    <P as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<P as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Vec<Q::Response>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, usize>) -> Result<Self::Response, Self::Error> {
+        let scale = self.alpha / batch.len() as f64;
+        let residuals: Vec<f64> = batch.iter().map(|t| self.td_error(&t.borrowed())).collect();
+
+        let responses = batch
+            .iter()
+            .zip(residuals)
+            .map(|(t, residual)| {
+                self.q_func.handle(StateActionUpdate {
+                    state: t.from.state(),
+                    action: t.action,
+                    error: scale * residual,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.sync_target();
+
+        Ok(responses)
+    }
+}
+
+impl<'m, S, Q, P> Handler<&'m Trajectory<S, usize>> for ExpectedSARSA<Q, P>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
+    P: EnumerablePolicy<&'m S>,
+
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+
+    <P as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<P as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Vec<Q::Response>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, traj: &'m Trajectory<S, usize>) -> Result<Self::Response, Self::Error> {
+        traj.iter()
+            .map(|t| {
+                let residual = self.td_error(&t);
+                let response = self.q_func.handle(StateActionUpdate {
+                    state: *t.from.state(),
+                    action: *t.action,
+                    error: self.alpha * residual,
+                })?;
+
+                self.sync_target();
+
+                Ok(response)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domains::Observation, fa::tabular::Table, policies::Random};
+    use ndarray::Array2;
+
+    fn agent() -> ExpectedSARSA<Table<Array2<f64>>, Random> {
+        ExpectedSARSA::new(Table::dense(Array2::zeros((2, 1))), Random::new(1), 1.0, 0.5)
+    }
+
+    #[test]
+    fn test_batch() {
+        let batch: Batch<usize, usize> = vec![
+            Transition {
+                from: Observation::Full(0),
+                action: 0,
+                reward: 1.0,
+                to: Observation::Full(1),
+            },
+            Transition {
+                from: Observation::Full(1),
+                action: 0,
+                reward: 1.0,
+                to: Observation::Full(0),
+            },
+        ];
+        let mut agent = agent();
+
+        agent.handle(&batch).unwrap();
+
+        assert_eq!(agent.q_func.evaluate((0usize, 0usize)), 0.5);
+        assert_eq!(agent.q_func.evaluate((1usize, 0usize)), 0.5);
+    }
+
+    #[test]
+    fn test_trajectory() {
+        let traj = Trajectory {
+            start: Observation::Full(0),
+            steps: vec![(Observation::Full(1), 0, 1.0), (Observation::Full(0), 0, 1.0)],
         };
+        let mut agent = agent();
 
-        self.q_func.handle(StateActionUpdate {
-            state: s,
-            action: t.action,
-            error: self.alpha * residual,
-        })
+        agent.handle(&traj).unwrap();
+
+        assert_eq!(agent.q_func.evaluate((0usize, 0usize)), 1.0);
+        assert_eq!(agent.q_func.evaluate((1usize, 0usize)), 1.5);
     }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    domains::Transition,
+    domains::{Batch, Trajectory, Transition},
     fa::StateActionUpdate,
     policies::Policy,
     Enumerable,
@@ -35,6 +35,8 @@ pub enum Error<EQ, ET> {
     TDEstError(ET),
 }
 
+type Outcome<RQ, RT, EQ, ET> = Result<Response<RQ, RT>, Error<EQ, ET>>;
+
 /// Greedy GQ control algorithm.
 ///
 /// Maei, Hamid R., et al. "Toward off-policy learning control with function
@@ -56,6 +58,78 @@ pub struct GreedyGQ<Q, T, P> {
     pub gamma: f64,
 }
 
+impl<Q, T, P> GreedyGQ<Q, T, P> {
+    /// Compute the TD error, the auxiliary estimate and the greedy successor
+    /// action for a single transition.
+    fn errors<'m, S>(&self, t: &Transition<&'m S, &'m usize>) -> (f64, f64, Option<usize>)
+    where
+        Q: Function<(&'m S,)> + Enumerable<(&'m S,)>,
+        Q::Output: IntoIterator<Item = f64> + std::ops::Index<usize, Output = f64>,
+        <Q::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+
+        T: Function<(&'m S, usize), Output = f64>,
+    {
+        let s = *t.from.state();
+
+        let qsa = self.fa_q.evaluate_index((s,), *t.action);
+        let td_est = self.fa_td.evaluate((s, *t.action));
+
+        if t.terminated() {
+            (t.reward - qsa, td_est, None)
+        } else {
+            let (na, qnsna) = self.fa_q.find_max((*t.to.state(),));
+
+            (t.reward + self.gamma * qnsna - qsa, td_est, Some(na))
+        }
+    }
+
+    fn apply<'m, S>(
+        &mut self,
+        t: &Transition<&'m S, &'m usize>,
+        (td_error, td_est, na): (f64, f64, Option<usize>),
+        scale: f64,
+    ) -> Outcome<Q::Response, T::Response, Q::Error, T::Error>
+    where
+        Q: Handler<StateActionUpdate<&'m S, usize>>,
+        T: Handler<StateActionUpdate<&'m S, usize>>,
+    {
+        let s = *t.from.state();
+
+        let mut q_response = self.fa_q
+            .handle(StateActionUpdate {
+                state: s,
+                action: *t.action,
+                error: scale * td_error,
+            })
+            .map_err(Error::QFuncError)?;
+
+        if let Some(na) = na {
+            q_response = self.fa_q
+                .handle(StateActionUpdate {
+                    state: *t.to.state(),
+                    action: na,
+                    error: -scale * self.gamma * td_est,
+                })
+                .map_err(Error::QFuncError)?;
+        }
+
+        let td_response = self.fa_td
+            .handle(StateActionUpdate {
+                state: s,
+                action: *t.action,
+                error: scale * (td_error - td_est),
+            })
+            .map_err(Error::TDEstError)?;
+
+        Ok(Response {
+            td_error,
+
+            q_response,
+            td_response,
+        })
+    }
+}
+
 impl<'m, S, Q, T, P> Handler<&'m Transition<S, P::Action>> for GreedyGQ<Q, T, P>
 where
     Q: Handler<StateActionUpdate<&'m S, usize>> + Function<(&'m S,)> + Enumerable<(&'m S,)>,
@@ -71,72 +145,64 @@ where
     type Error = Error<Q::Error, T::Error>;
 
     fn handle(&mut self, t: &'m Transition<S, P::Action>) -> Result<Self::Response, Self::Error> {
-        let s = t.from.state();
+        let t = t.borrowed();
+        let errors = self.errors(&t);
 
-        let qsa = self.fa_q.evaluate_index((s,), t.action);
-        let td_est = self.fa_td.evaluate((s, t.action));
+        self.apply(&t, errors, 1.0)
+    }
+}
 
-        if t.terminated() {
-            let td_error = t.reward - qsa;
+impl<'m, S, Q, T, P> Handler<&'m Batch<S, P::Action>> for GreedyGQ<Q, T, P>
+where
+    Q: Handler<StateActionUpdate<&'m S, usize>> + Function<(&'m S,)> + Enumerable<(&'m S,)>,
 
-            let q_response = self.fa_q
-                .handle(StateActionUpdate {
-                    state: s,
-                    action: t.action,
-                    error: td_error,
-                })
-                .map_err(|e| Error::QFuncError(e))?;
+    Q::Output: IntoIterator<Item = f64> + std::ops::Index<usize, Output = f64>,
+    <Q::Output as IntoIterator>::IntoIter: ExactSizeIterator,
 
-            let td_response = self.fa_td
-                .handle(StateActionUpdate {
-                    state: s,
-                    action: t.action,
-                    error: td_error - td_est,
-                })
-                .map_err(|e| Error::TDEstError(e))?;
+    T: Handler<StateActionUpdate<&'m S, usize>> + Function<(&'m S, usize), Output = f64>,
 
-            Ok(Response {
-                td_error,
+    P: Policy<&'m S, Action = usize>,
+{
+    type Response = Vec<Response<Q::Response, T::Response>>;
+    type Error = Error<Q::Error, T::Error>;
 
-                q_response,
-                td_response,
-            })
-        } else {
-            let ns = t.to.state();
-            let (na, qnsna) = self.fa_q.find_max((ns,));
+    fn handle(&mut self, batch: &'m Batch<S, P::Action>) -> Result<Self::Response, Self::Error> {
+        let scale = 1.0 / batch.len() as f64;
+        let errors: Vec<_> = batch.iter().map(|t| self.errors(&t.borrowed())).collect();
 
-            let td_error = t.reward + self.gamma * qnsna - qsa;
+        batch
+            .iter()
+            .zip(errors)
+            .map(|(t, errors)| self.apply(&t.borrowed(), errors, scale))
+            .collect()
+    }
+}
 
-            let q_response = self.fa_q
-                .handle(StateActionUpdate {
-                    state: s,
-                    action: t.action,
-                    error: td_error,
-                })
-                .and_then(|_| {
-                    self.fa_q
-                        .handle(StateActionUpdate {
-                            state: ns,
-                            action: na,
-                            error: -self.gamma * td_est,
-                        })
-                })
-                .map_err(|e| Error::QFuncError(e))?;
+impl<'m, S, Q, T, P> Handler<&'m Trajectory<S, P::Action>> for GreedyGQ<Q, T, P>
+where
+    Q: Handler<StateActionUpdate<&'m S, usize>> + Function<(&'m S,)> + Enumerable<(&'m S,)>,
 
-            let td_response = self.fa_td
-                .handle(StateActionUpdate {
-                    state: s,
-                    action: t.action,
-                    error: td_error - td_est,
-                })
-                .map_err(|e| Error::TDEstError(e))?;
+    Q::Output: IntoIterator<Item = f64> + std::ops::Index<usize, Output = f64>,
+    <Q::Output as IntoIterator>::IntoIter: ExactSizeIterator,
 
-            Ok(Response {
-                td_error,
+    T: Handler<StateActionUpdate<&'m S, usize>> + Function<(&'m S, usize), Output = f64>,
 
-                q_response,
-                td_response,
+    P: Policy<&'m S, Action = usize>,
+{
+    type Response = Vec<Response<Q::Response, T::Response>>;
+    type Error = Error<Q::Error, T::Error>;
+
+    fn handle(
+        &mut self,
+        traj: &'m Trajectory<S, P::Action>,
+    ) -> Result<Self::Response, Self::Error>
+    {
+        traj.iter()
+            .map(|t| {
+                let errors = self.errors(&t);
+
+                self.apply(&t, errors, 1.0)
             })
-        }
+            .collect()
     }
 }
@@ -5,6 +5,7 @@ pub mod pal;
 pub mod q_lambda;
 pub mod q_learning;
 pub mod q_sigma;
+pub mod soft_q_learning;
 
 pub use self::{
     greedy_gq::GreedyGQ,
@@ -13,14 +14,32 @@ pub use self::{
     q_lambda::QLambda,
     q_learning::QLearning,
     q_sigma::QSigma,
+    soft_q_learning::SoftQLearning,
 };
 
 // On-policy:
 pub mod expected_sarsa;
 pub mod sarsa;
 pub mod sarsa_lambda;
+pub mod soft_expected_sarsa;
 
-pub use self::{expected_sarsa::ExpectedSARSA, sarsa::SARSA, sarsa_lambda::SARSALambda};
+pub use self::{
+    expected_sarsa::ExpectedSARSA,
+    sarsa::SARSA,
+    sarsa_lambda::SARSALambda,
+    soft_expected_sarsa::SoftExpectedSARSA,
+};
+
+// Average-reward:
+pub mod differential_sarsa;
+pub mod r_learning;
+
+pub use self::{differential_sarsa::DifferentialSARSA, r_learning::RLearning};
+
+// Exploration:
+pub mod count_bonus;
+
+pub use self::count_bonus::CountBonus;
 
 // TODO:
 // PQ(lambda) - http://proceedings.mlr.press/v32/sutton14.pdf
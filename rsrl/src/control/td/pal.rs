@@ -1,5 +1,5 @@
 use crate::{
-    domains::Transition,
+    domains::{Batch, Trajectory, Transition},
     fa::StateActionUpdate,
     utils::argmax_first,
     Enumerable,
@@ -15,6 +15,11 @@ use std::ops::Index;
 /// - Bellemare, Marc G., et al. "Increasing the Action Gap: New Operators for
 /// Reinforcement Learning." AAAI. 2016.
 #[derive(Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct PAL<Q> {
     #[weights]
     pub q_func: Q,
@@ -23,6 +28,34 @@ pub struct PAL<Q> {
     pub gamma: f64,
 }
 
+impl<Q> PAL<Q> {
+    fn td_error<'m, S>(&self, t: &Transition<&'m S, &'m usize>) -> f64
+    where
+        Q: Enumerable<(&'m S,), Output = Vec<f64>>,
+        <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator,
+        <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        let s = *t.from.state();
+        let a = *t.action;
+
+        if t.terminated() {
+            t.reward - self.q_func.evaluate_index((s,), a)
+        } else {
+            let ns = *t.to.state();
+            let qs = self.q_func.evaluate((s,));
+            let nqs = self.q_func.evaluate((ns,));
+
+            let a_star = argmax_first(qs.iter().copied()).0;
+            let na_star = argmax_first(nqs.iter().copied()).0;
+
+            let td_error = t.reward + self.gamma * nqs[a_star] - qs[a];
+            let al_error = td_error - self.alpha * (qs[a_star] - qs[a]);
+
+            al_error.max(td_error - self.alpha * (nqs[na_star] - nqs[a]))
+        }
+    }
+}
+
 impl<'m, S, Q> Handler<&'m Transition<S, usize>> for PAL<Q>
 where
     Q: Enumerable<(&'m S,), Output = Vec<f64>> + Handler<StateActionUpdate<&'m S, usize, f64>>,
@@ -33,28 +66,63 @@ where
     type Error = Q::Error;
 
     fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<Self::Response, Self::Error> {
-        let s = t.from.state();
-
-        let residual = if t.terminated() {
-            t.reward - self.q_func.evaluate_index((s,), t.action)
-        } else {
-            let ns = t.to.state();
-            let qs = self.q_func.evaluate((s,));
-            let nqs = self.q_func.evaluate((ns,));
-
-            let a_star = argmax_first(qs.iter().copied()).0;
-            let na_star = argmax_first(nqs.iter().copied()).0;
-
-            let td_error = t.reward + self.gamma * nqs[a_star] - qs[t.action];
-            let al_error = td_error - self.alpha * (qs[a_star] - qs[t.action]);
-
-            al_error.max(td_error - self.alpha * (nqs[na_star] - nqs[t.action]))
-        };
+        let residual = self.td_error(&t.borrowed());
 
         self.q_func.handle(StateActionUpdate {
-            state: s,
+            state: t.from.state(),
             action: t.action,
             error: self.alpha * residual,
         })
     }
 }
+
+impl<'m, S, Q> Handler<&'m Batch<S, usize>> for PAL<Q>
+where
+    Q: Enumerable<(&'m S,), Output = Vec<f64>> + Handler<StateActionUpdate<&'m S, usize, f64>>,
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Vec<Q::Response>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, usize>) -> Result<Self::Response, Self::Error> {
+        let scale = self.alpha / batch.len() as f64;
+        let residuals: Vec<f64> = batch.iter().map(|t| self.td_error(&t.borrowed())).collect();
+
+        batch
+            .iter()
+            .zip(residuals)
+            .map(|(t, residual)| {
+                self.q_func.handle(StateActionUpdate {
+                    state: t.from.state(),
+                    action: t.action,
+                    error: scale * residual,
+                })
+            })
+            .collect()
+    }
+}
+
+impl<'m, S, Q> Handler<&'m Trajectory<S, usize>> for PAL<Q>
+where
+    Q: Enumerable<(&'m S,), Output = Vec<f64>> + Handler<StateActionUpdate<&'m S, usize, f64>>,
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Vec<Q::Response>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, traj: &'m Trajectory<S, usize>) -> Result<Self::Response, Self::Error> {
+        traj.iter()
+            .map(|t| {
+                let residual = self.td_error(&t);
+
+                self.q_func.handle(StateActionUpdate {
+                    state: *t.from.state(),
+                    action: *t.action,
+                    error: self.alpha * residual,
+                })
+            })
+            .collect()
+    }
+}
@@ -1,10 +1,10 @@
 use crate::{
-    domains::Transition,
-    fa::StateActionUpdate,
+    domains::{Batch, Trajectory, Transition},
+    fa::{StateActionUpdate, TargetFunction},
+    params::Parameterised,
     Enumerable,
     Function,
     Handler,
-    Parameterised,
 };
 use std::ops::Index;
 
@@ -21,6 +21,9 @@ pub struct Response<R> {
 
 /// Watkins' Q-learning.
 ///
+/// Bootstrap targets are computed from `target`, when present, in place of the
+/// online `q_func`; this yields the DQN-style learner of Mnih et al. (2015).
+///
 /// # References
 /// - Watkins, C. J. C. H. (1989). Learning from Delayed Rewards. Ph.D. thesis,
 /// Cambridge University.
@@ -37,11 +40,61 @@ pub struct QLearning<Q> {
     pub q_func: Q,
 
     pub gamma: f64,
+
+    pub target: Option<TargetFunction<Q>>,
+}
+
+impl<Q> QLearning<Q> {
+    pub fn new(q_func: Q, gamma: f64) -> Self {
+        QLearning {
+            q_func,
+            gamma,
+
+            target: None,
+        }
+    }
+
+    /// Compute bootstrap targets from a separate target function.
+    pub fn with_target(self, target: TargetFunction<Q>) -> Self {
+        QLearning {
+            target: Some(target),
+            ..self
+        }
+    }
+}
+
+impl<Q: Parameterised> QLearning<Q> {
+    fn td_error<'m, S>(&self, t: &Transition<&'m S, &'m usize>) -> f64
+    where
+        Q: Enumerable<(&'m S,)>,
+        <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+        <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        let qsa = self.q_func.evaluate_index((*t.from.state(),), *t.action);
+
+        if t.terminated() {
+            t.reward - qsa
+        } else {
+            let ns = *t.to.state();
+            let (_, nqsna) = match self.target {
+                Some(ref target) => target.find_max((ns,)),
+                None => self.q_func.find_max((ns,)),
+            };
+
+            t.reward + self.gamma * nqsna - qsa
+        }
+    }
+
+    fn sync_target(&mut self) {
+        if let Some(ref mut target) = self.target {
+            target.update(&self.q_func);
+        }
+    }
 }
 
 impl<'m, S, Q> Handler<&'m Transition<S, usize>> for QLearning<Q>
 where
-    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>>,
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
     <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
     <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
 {
@@ -49,24 +102,134 @@ where
     type Error = Q::Error;
 
     fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<Self::Response, Self::Error> {
-        let state = t.from.state();
-        let qsa = self.q_func.evaluate_index((state,), t.action);
+        let error = self.td_error(&t.borrowed());
+        let q_res = self.q_func.handle(StateActionUpdate {
+            state: t.from.state(),
+            action: t.action,
+            error,
+        })?;
 
-        let error = if t.terminated() {
-            t.reward - qsa
-        } else {
-            let ns = t.to.state();
-            let (_, nqsna) = self.q_func.find_max((ns,));
+        self.sync_target();
 
-            t.reward + self.gamma * nqsna - qsa
-        };
+        Ok(Response { q_res, error })
+    }
+}
 
-        self.q_func
-            .handle(StateActionUpdate {
-                state,
-                action: t.action,
-                error,
+impl<'m, S, Q> Handler<&'m Batch<S, usize>> for QLearning<Q>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Vec<Response<Q::Response>>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, usize>) -> Result<Self::Response, Self::Error> {
+        let n = batch.len() as f64;
+        let errors: Vec<f64> = batch.iter().map(|t| self.td_error(&t.borrowed())).collect();
+
+        let responses = batch
+            .iter()
+            .zip(errors)
+            .map(|(t, error)| {
+                self.q_func
+                    .handle(StateActionUpdate {
+                        state: t.from.state(),
+                        action: t.action,
+                        error: error / n,
+                    })
+                    .map(|q_res| Response { q_res, error })
             })
-            .map(|q_res| Response { q_res, error })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.sync_target();
+
+        Ok(responses)
+    }
+}
+
+impl<'m, S, Q> Handler<&'m Trajectory<S, usize>> for QLearning<Q>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Vec<Response<Q::Response>>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, traj: &'m Trajectory<S, usize>) -> Result<Self::Response, Self::Error> {
+        traj.iter()
+            .map(|t| {
+                let error = self.td_error(&t);
+                let q_res = self.q_func.handle(StateActionUpdate {
+                    state: *t.from.state(),
+                    action: *t.action,
+                    error,
+                })?;
+
+                self.sync_target();
+
+                Ok(Response { q_res, error })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domains::Observation, fa::tabular::Table};
+    use ndarray::Array2;
+
+    fn transition(from: usize, to: usize) -> Transition<usize, usize> {
+        Transition {
+            from: Observation::Full(from),
+            action: 0,
+            reward: 1.0,
+            to: Observation::Full(to),
+        }
+    }
+
+    fn agent(period: usize) -> QLearning<Table<Array2<f64>>> {
+        let target = TargetFunction::hard(Table::dense(Array2::zeros((2, 1))), period);
+
+        QLearning::new(Table::dense(Array2::zeros((2, 1))), 0.5).with_target(target)
+    }
+
+    #[test]
+    fn test_batch() {
+        let batch = vec![transition(0, 1), transition(1, 0)];
+        let mut agent = agent(2);
+        let res = agent.handle(&batch).unwrap();
+
+        // Errors use the pre-update weights and each update is scaled by 1/n.
+        assert_eq!(res.iter().map(|r| r.error).collect::<Vec<_>>(), vec![1.0, 1.0]);
+        assert_eq!(agent.q_func.evaluate((0usize, 0usize)), 0.5);
+        assert_eq!(agent.q_func.evaluate((1usize, 0usize)), 0.5);
+
+        // The target is synced once per batch, so a period of two is not reached.
+        let target = agent.target.as_ref().unwrap();
+
+        assert_eq!(target.evaluate((0usize, 0usize)), 0.0);
+    }
+
+    #[test]
+    fn test_trajectory() {
+        let traj = Trajectory {
+            start: Observation::Full(0),
+            steps: vec![(Observation::Full(1), 0, 1.0), (Observation::Full(0), 0, 1.0)],
+        };
+        let mut agent = agent(1);
+        let res = agent.handle(&traj).unwrap();
+
+        // The second error bootstraps from the target synced after the first
+        // update: 1 + 0.5 * Q(0) = 1.5.
+        assert_eq!(res.iter().map(|r| r.error).collect::<Vec<_>>(), vec![1.0, 1.5]);
+        assert_eq!(agent.q_func.evaluate((0usize, 0usize)), 1.0);
+        assert_eq!(agent.q_func.evaluate((1usize, 0usize)), 1.5);
+
+        let target = agent.target.as_ref().unwrap();
+
+        assert_eq!(target.evaluate((1usize, 0usize)), 1.5);
     }
 }
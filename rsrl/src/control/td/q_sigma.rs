@@ -11,6 +11,11 @@ use crate::{
 use rand::thread_rng;
 use std::{collections::VecDeque, ops::Index};
 
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 struct BackupEntry<S> {
     pub s: S,
     pub a: usize,
@@ -23,6 +28,11 @@ struct BackupEntry<S> {
     pub mu: f64,
 }
 
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 struct Backup<S> {
     n_steps: usize,
     entries: VecDeque<BackupEntry<S>>,
@@ -77,6 +87,11 @@ impl<S> Backup<S> {
 /// (2017). Multi-step Reinforcement Learning: A Unifying Algorithm. arXiv
 /// preprint arXiv:1703.01327.
 #[derive(Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct QSigma<S, Q, P> {
     #[weights]
     pub q_func: Q,
@@ -0,0 +1,144 @@
+use crate::{
+    domains::Transition,
+    fa::StateActionUpdate,
+    utils::argmax_first,
+    Enumerable,
+    Function,
+    Handler,
+    Parameterised,
+};
+use std::ops::Index;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Response<R> {
+    pub q_res: R,
+    pub error: f64,
+    pub rho: f64,
+}
+
+/// Schwartz's R-learning for average-reward control.
+///
+/// The agent maintains an estimate of the average reward per step, `rho`, in
+/// place of a discount factor. The estimate is only adjusted, with step size
+/// `beta`, on transitions in which the greedy action was taken; the step size
+/// of the value update is left to `q_func`.
+///
+/// # References
+/// - Schwartz, A. (1993). A reinforcement learning method for maximizing
+/// undiscounted rewards. In Proceedings of the Tenth International Conference
+/// on Machine Learning, pp. 298–305.
+/// - Mahadevan, S. (1996). Average reward reinforcement learning: Foundations,
+/// algorithms, and empirical results. Machine Learning, 22:159–195.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct RLearning<Q> {
+    #[weights]
+    pub q_func: Q,
+
+    pub beta: f64,
+
+    pub rho: f64,
+}
+
+impl<Q> RLearning<Q> {
+    pub fn new(q_func: Q, beta: f64) -> Self {
+        RLearning {
+            q_func,
+
+            beta,
+
+            rho: 0.0,
+        }
+    }
+}
+
+impl<'m, S, Q> Handler<&'m Transition<S, usize>> for RLearning<Q>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>>,
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Response<Q::Response>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<Self::Response, Self::Error> {
+        let s = t.from.state();
+        let qs = self.q_func.evaluate((s,));
+        let qsa = qs[t.action];
+
+        let (a_star, qs_max) = argmax_first(qs);
+
+        let nqs_max = if t.terminated() {
+            0.0
+        } else {
+            self.q_func.find_max((t.to.state(),)).1
+        };
+
+        let error = t.reward - self.rho + nqs_max - qsa;
+
+        if t.action == a_star {
+            self.rho += self.beta * (t.reward - self.rho + nqs_max - qs_max);
+        }
+
+        self.q_func
+            .handle(StateActionUpdate {
+                state: s,
+                action: t.action,
+                error,
+            })
+            .map(|q_res| Response {
+                q_res,
+                error,
+                rho: self.rho,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domains::Observation, fa::tabular::Table};
+    use ndarray::Array2;
+
+    fn transition(from: usize, action: usize, reward: f64) -> Transition<usize, usize> {
+        Transition {
+            from: Observation::Full(from),
+            action,
+            reward,
+            to: Observation::Full(1 - from),
+        }
+    }
+
+    #[test]
+    fn test_average_reward() {
+        let mut agent = RLearning::new(Table::dense(Array2::zeros((2, 1))), 0.1);
+
+        // Cycle between two states with rewards 1 and 0.
+        for i in 0..200 {
+            agent.handle(&transition(i % 2, 0, (1 - i % 2) as f64)).unwrap();
+        }
+
+        assert!((agent.rho - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_exploratory_action() {
+        let mut qs = Array2::zeros((2, 2));
+        qs[(0, 1)] = 1.0;
+
+        let mut agent = RLearning::new(Table::dense(qs), 0.1);
+        let res = agent.handle(&transition(0, 0, 1.0)).unwrap();
+
+        assert_eq!(res.rho, 0.0);
+        assert_eq!(res.error, 1.0);
+    }
+}
@@ -1,10 +1,10 @@
 use crate::{
-    domains::Transition,
-    fa::StateActionUpdate,
+    domains::{Batch, Trajectory, Transition},
+    fa::{StateActionUpdate, TargetFunction},
+    params::Parameterised,
     policies::Policy,
     Function,
     Handler,
-    Parameterised,
 };
 use rand::thread_rng;
 
@@ -21,6 +21,9 @@ pub struct Response<R> {
 
 /// On-policy variant of Watkins' Q-learning (aka "modified Q-learning").
 ///
+/// Bootstrap targets are computed from `target`, when present, in place of the
+/// online `q_func`.
+///
 /// # References
 /// - Rummery, G. A. (1995). Problem Solving with Reinforcement Learning. Ph.D
 /// thesis, Cambridge University.
@@ -38,39 +41,159 @@ pub struct SARSA<Q, P> {
     pub policy: P,
 
     pub gamma: f64,
+
+    pub target: Option<TargetFunction<Q>>,
+}
+
+impl<Q, P> SARSA<Q, P> {
+    pub fn new(q_func: Q, policy: P, gamma: f64) -> Self {
+        SARSA {
+            q_func,
+            policy,
+
+            gamma,
+
+            target: None,
+        }
+    }
+
+    /// Compute bootstrap targets from a separate target function.
+    pub fn with_target(self, target: TargetFunction<Q>) -> Self {
+        SARSA {
+            target: Some(target),
+            ..self
+        }
+    }
+}
+
+impl<Q: Parameterised, P> SARSA<Q, P> {
+    fn td_error<'m, S>(&self, t: &Transition<&'m S, &'m P::Action>) -> f64
+    where
+        Q: Function<(&'m S, P::Action), Output = f64>
+            + for<'a> Function<(&'m S, &'a P::Action), Output = f64>,
+        P: Policy<&'m S>,
+    {
+        let qsa = self.q_func.evaluate((*t.from.state(), t.action));
+
+        if t.terminated() {
+            t.reward - qsa
+        } else {
+            let ns = *t.to.state();
+            let na = self.policy.sample(&mut thread_rng(), ns);
+            let nqsna = match self.target {
+                Some(ref target) => target.evaluate((ns, na)),
+                None => self.q_func.evaluate((ns, na)),
+            };
+
+            t.reward + self.gamma * nqsna - qsa
+        }
+    }
+
+    fn sync_target(&mut self) {
+        if let Some(ref mut target) = self.target {
+            target.update(&self.q_func);
+        }
+    }
 }
 
 impl<'m, S, Q, P> Handler<&'m Transition<S, P::Action>> for SARSA<Q, P>
 where
     Q: Function<(&'m S, P::Action), Output = f64>
         + for<'a> Function<(&'m S, &'a P::Action), Output = f64>
-        + Handler<StateActionUpdate<&'m S, &'m P::Action>>,
+        + Handler<StateActionUpdate<&'m S, &'m P::Action>>
+        + Parameterised,
     P: Policy<&'m S>,
 {
     type Response = Response<Q::Response>;
     type Error = Q::Error;
 
     fn handle(&mut self, t: &'m Transition<S, P::Action>) -> Result<Self::Response, Self::Error> {
-        let s = t.from.state();
-        let qsa = self.q_func.evaluate((s, &t.action));
-
-        let residual = if t.terminated() {
-            t.reward - qsa
-        } else {
-            let ns = t.to.state();
-            let na = self.policy.sample(&mut thread_rng(), ns);
-            let nqsna = self.q_func.evaluate((ns, na));
-
-            t.reward + self.gamma * nqsna - qsa
-        };
-
-        self.q_func.handle(StateActionUpdate {
-            state: s,
+        let residual = self.td_error(&t.borrowed());
+        let qfunc_response = self.q_func.handle(StateActionUpdate {
+            state: t.from.state(),
             action: &t.action,
             error: residual,
-        }).map(|r| Response {
+        })?;
+
+        self.sync_target();
+
+        Ok(Response {
             td_error: residual,
-            qfunc_response: r,
+            qfunc_response,
         })
     }
 }
+
+impl<'m, S, Q, P> Handler<&'m Batch<S, P::Action>> for SARSA<Q, P>
+where
+    Q: Function<(&'m S, P::Action), Output = f64>
+        + for<'a> Function<(&'m S, &'a P::Action), Output = f64>
+        + Handler<StateActionUpdate<&'m S, &'m P::Action>>
+        + Parameterised,
+    P: Policy<&'m S>,
+{
+    type Response = Vec<Response<Q::Response>>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, P::Action>) -> Result<Self::Response, Self::Error> {
+        let n = batch.len() as f64;
+        let residuals: Vec<f64> = batch.iter().map(|t| self.td_error(&t.borrowed())).collect();
+
+        let responses = batch
+            .iter()
+            .zip(residuals)
+            .map(|(t, residual)| {
+                self.q_func
+                    .handle(StateActionUpdate {
+                        state: t.from.state(),
+                        action: &t.action,
+                        error: residual / n,
+                    })
+                    .map(|qfunc_response| Response {
+                        td_error: residual,
+                        qfunc_response,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.sync_target();
+
+        Ok(responses)
+    }
+}
+
+impl<'m, S, Q, P> Handler<&'m Trajectory<S, P::Action>> for SARSA<Q, P>
+where
+    Q: Function<(&'m S, P::Action), Output = f64>
+        + for<'a> Function<(&'m S, &'a P::Action), Output = f64>
+        + Handler<StateActionUpdate<&'m S, &'m P::Action>>
+        + Parameterised,
+    P: Policy<&'m S>,
+{
+    type Response = Vec<Response<Q::Response>>;
+    type Error = Q::Error;
+
+    fn handle(
+        &mut self,
+        traj: &'m Trajectory<S, P::Action>,
+    ) -> Result<Self::Response, Self::Error>
+    {
+        traj.iter()
+            .map(|t| {
+                let residual = self.td_error(&t);
+                let qfunc_response = self.q_func.handle(StateActionUpdate {
+                    state: *t.from.state(),
+                    action: t.action,
+                    error: residual,
+                })?;
+
+                self.sync_target();
+
+                Ok(Response {
+                    td_error: residual,
+                    qfunc_response,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{
+    domains::{Batch, Trajectory, Transition},
+    fa::{StateActionUpdate, TargetFunction},
+    params::Parameterised,
+    policies::EnumerablePolicy,
+    Enumerable,
+    Function,
+    Handler,
+};
+use std::ops::Index;
+
+/// Entropy-regularised variant of Expected SARSA.
+///
+/// The expected next action-value is augmented with the entropy of `policy`,
+/// weighted by the temperature `tau`, giving the bootstrap target
+/// `Σ π(a' | s') [Q(s', a') - τ ln π(a' | s')]`. When `policy` is a Boltzmann
+/// distribution over `q_func` with the same temperature, this coincides with
+/// the log-sum-exp backup of `SoftQLearning`. Bootstrap targets are computed
+/// from `target`, when present, in place of the online `q_func`.
+///
+/// # References
+/// - Haarnoja, T., Tang, H., Abbeel, P., Levine, S. (2017). Reinforcement
+/// learning with deep energy-based policies. In Proceedings of ICML, pp.
+/// 1352-1361.
+/// - van Seijen, H., van Hasselt, H., Whiteson, S., Wiering, M. (2009). A
+/// theoretical and empirical analysis of Expected Sarsa. In Proceedings of the
+/// IEEE Symposium on Adaptive Dynamic Programming and Reinforcement Learning,
+/// pp. 177–184.
+#[derive(Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct SoftExpectedSARSA<Q, P> {
+    #[weights]
+    pub q_func: Q,
+    pub policy: P,
+
+    pub alpha: f64,
+    pub tau: f64,
+    pub gamma: f64,
+
+    pub target: Option<TargetFunction<Q>>,
+}
+
+impl<Q, P> SoftExpectedSARSA<Q, P> {
+    pub fn new(q_func: Q, policy: P, alpha: f64, tau: f64, gamma: f64) -> Self {
+        SoftExpectedSARSA {
+            q_func,
+            policy,
+
+            alpha,
+            tau,
+            gamma,
+
+            target: None,
+        }
+    }
+
+    /// Compute bootstrap targets from a separate target function.
+    pub fn with_target(self, target: TargetFunction<Q>) -> Self {
+        SoftExpectedSARSA {
+            target: Some(target),
+            ..self
+        }
+    }
+}
+
+impl<Q: Parameterised, P> SoftExpectedSARSA<Q, P> {
+    fn td_error<'m, S>(&self, t: &Transition<&'m S, &'m usize>) -> f64
+    where
+        Q: Enumerable<(&'m S,)>,
+        P: EnumerablePolicy<&'m S>,
+
+        <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+        <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+
+        <P as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+        <<P as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        let qsa = self.q_func.evaluate_index((*t.from.state(),), *t.action);
+
+        if t.terminated() {
+            t.reward - qsa
+        } else {
+            let ns = *t.to.state();
+            let nqs = match self.target {
+                Some(ref target) => target.evaluate((ns,)),
+                None => self.q_func.evaluate((ns,)),
+            };
+            let exp_nv = nqs
+                .into_iter()
+                .zip(self.policy.evaluate((ns,)))
+                .fold(0.0, |acc, (q, p)| {
+                    if p > 0.0 {
+                        acc + p * (q - self.tau * p.ln())
+                    } else {
+                        acc
+                    }
+                });
+
+            t.reward + self.gamma * exp_nv - qsa
+        }
+    }
+
+    fn sync_target(&mut self) {
+        if let Some(ref mut target) = self.target {
+            target.update(&self.q_func);
+        }
+    }
+}
+
+impl<'m, S, Q, P> Handler<&'m Transition<S, usize>> for SoftExpectedSARSA<Q, P>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
+    P: EnumerablePolicy<&'m S>,
+
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+
+    <P as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<P as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Q::Response;
+    type Error = Q::Error;
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<Self::Response, Self::Error> {
+        let residual = self.td_error(&t.borrowed());
+        let response = self.q_func.handle(StateActionUpdate {
+            state: t.from.state(),
+            action: t.action,
+            error: self.alpha * residual,
+        })?;
+
+        self.sync_target();
+
+        Ok(response)
+    }
+}
+
+impl<'m, S, Q, P> Handler<&'m Batch<S, usize>> for SoftExpectedSARSA<Q, P>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
+    P: EnumerablePolicy<&'m S>,
+
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+
+    <P as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<P as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Vec<Q::Response>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, usize>) -> Result<Self::Response, Self::Error> {
+        let scale = self.alpha / batch.len() as f64;
+        let residuals: Vec<f64> = batch.iter().map(|t| self.td_error(&t.borrowed())).collect();
+
+        let responses = batch
+            .iter()
+            .zip(residuals)
+            .map(|(t, residual)| {
+                self.q_func.handle(StateActionUpdate {
+                    state: t.from.state(),
+                    action: t.action,
+                    error: scale * residual,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.sync_target();
+
+        Ok(responses)
+    }
+}
+
+impl<'m, S, Q, P> Handler<&'m Trajectory<S, usize>> for SoftExpectedSARSA<Q, P>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
+    P: EnumerablePolicy<&'m S>,
+
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+
+    <P as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<P as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Vec<Q::Response>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, traj: &'m Trajectory<S, usize>) -> Result<Self::Response, Self::Error> {
+        traj.iter()
+            .map(|t| {
+                let residual = self.td_error(&t);
+                let response = self.q_func.handle(StateActionUpdate {
+                    state: *t.from.state(),
+                    action: *t.action,
+                    error: self.alpha * residual,
+                })?;
+
+                self.sync_target();
+
+                Ok(response)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        control::td::SoftQLearning,
+        domains::Observation,
+        fa::tabular::Table,
+        policies::Softmax,
+    };
+    use ndarray::Array2;
+
+    #[test]
+    fn test_log_sum_exp_target() {
+        let qs = Array2::from_shape_vec((2, 3), vec![0.5, 0.0, -0.5, 1.0, 3.0, 2.0]).unwrap();
+        let t = Transition {
+            from: Observation::Full(0usize),
+            action: 0usize,
+            reward: 1.0,
+            to: Observation::Full(1usize),
+        };
+
+        for &tau in &[0.5, 1.0, 2.0] {
+            let mut soft_q = SoftQLearning::new(Table::dense(qs.clone()), tau, 0.9);
+            let soft_es = SoftExpectedSARSA::new(
+                Table::dense(qs.clone()),
+                Softmax::new(Table::dense(qs.clone()), tau),
+                1.0,
+                tau,
+                0.9,
+            );
+
+            let error = soft_es.td_error(&t.borrowed());
+
+            assert!((soft_q.handle(&t).unwrap().error - error).abs() < 1e-10);
+        }
+    }
+}
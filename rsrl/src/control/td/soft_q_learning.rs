@@ -0,0 +1,244 @@
+use crate::{
+    domains::{Batch, Trajectory, Transition},
+    fa::{StateActionUpdate, TargetFunction},
+    params::Parameterised,
+    utils::{log_sum_exp, mellowmax},
+    Enumerable,
+    Function,
+    Handler,
+};
+use std::ops::Index;
+
+/// Soft maximum operator used to compute bootstrap targets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum Backup {
+    /// Log-sum-exp, `τ ln Σ exp(Q(s, a) / τ)`.
+    LogSumExp,
+
+    /// Mellowmax, `τ ln (Σ exp(Q(s, a) / τ) / n)`.
+    Mellowmax,
+}
+
+impl Backup {
+    /// Apply the operator to a set of action-values with temperature `tau`.
+    pub fn apply(&self, values: &[f64], tau: f64) -> f64 {
+        match *self {
+            Backup::LogSumExp => log_sum_exp(values, tau),
+            Backup::Mellowmax => mellowmax(values, tau),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Response<R> {
+    pub q_res: R,
+    pub error: f64,
+}
+
+/// Soft Q-learning.
+///
+/// The hard maximum of `QLearning` is replaced with a smooth `backup` operator
+/// at temperature `tau`; as `tau` tends to zero both operators recover the
+/// standard Q-learning target. The log-sum-exp backup corresponds to the
+/// entropy-regularised (soft) Bellman optimality equation, while mellowmax is a
+/// non-expansion and is thus better behaved under function approximation.
+/// Bootstrap targets are computed from `target`, when present, in place of the
+/// online `q_func`.
+///
+/// # References
+/// - Haarnoja, T., Tang, H., Abbeel, P., Levine, S. (2017). Reinforcement
+/// learning with deep energy-based policies. In Proceedings of ICML, pp.
+/// 1352-1361.
+/// - Asadi, K., Littman, M. L. (2017). An alternative softmax operator for
+/// reinforcement learning. In Proceedings of ICML, pp. 243-252.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct SoftQLearning<Q> {
+    #[weights]
+    pub q_func: Q,
+    pub backup: Backup,
+
+    pub tau: f64,
+    pub gamma: f64,
+
+    pub target: Option<TargetFunction<Q>>,
+}
+
+impl<Q> SoftQLearning<Q> {
+    pub fn new(q_func: Q, tau: f64, gamma: f64) -> Self {
+        SoftQLearning {
+            q_func,
+            backup: Backup::LogSumExp,
+
+            tau,
+            gamma,
+
+            target: None,
+        }
+    }
+
+    pub fn with_backup(self, backup: Backup) -> Self { SoftQLearning { backup, ..self } }
+
+    /// Compute bootstrap targets from a separate target function.
+    pub fn with_target(self, target: TargetFunction<Q>) -> Self {
+        SoftQLearning {
+            target: Some(target),
+            ..self
+        }
+    }
+}
+
+impl<Q: Parameterised> SoftQLearning<Q> {
+    fn td_error<'m, S>(&self, t: &Transition<&'m S, &'m usize>) -> f64
+    where
+        Q: Enumerable<(&'m S,)>,
+        <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+        <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        let qsa = self.q_func.evaluate_index((*t.from.state(),), *t.action);
+
+        if t.terminated() {
+            t.reward - qsa
+        } else {
+            let ns = *t.to.state();
+            let nqs: Vec<f64> = match self.target {
+                Some(ref target) => target.evaluate((ns,)).into_iter().collect(),
+                None => self.q_func.evaluate((ns,)).into_iter().collect(),
+            };
+
+            t.reward + self.gamma * self.backup.apply(&nqs, self.tau) - qsa
+        }
+    }
+
+    fn sync_target(&mut self) {
+        if let Some(ref mut target) = self.target {
+            target.update(&self.q_func);
+        }
+    }
+}
+
+impl<'m, S, Q> Handler<&'m Transition<S, usize>> for SoftQLearning<Q>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Response<Q::Response>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<Self::Response, Self::Error> {
+        let error = self.td_error(&t.borrowed());
+        let q_res = self.q_func.handle(StateActionUpdate {
+            state: t.from.state(),
+            action: t.action,
+            error,
+        })?;
+
+        self.sync_target();
+
+        Ok(Response { q_res, error })
+    }
+}
+
+impl<'m, S, Q> Handler<&'m Batch<S, usize>> for SoftQLearning<Q>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Vec<Response<Q::Response>>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, usize>) -> Result<Self::Response, Self::Error> {
+        let n = batch.len() as f64;
+        let errors: Vec<f64> = batch.iter().map(|t| self.td_error(&t.borrowed())).collect();
+
+        let responses = batch
+            .iter()
+            .zip(errors)
+            .map(|(t, error)| {
+                self.q_func
+                    .handle(StateActionUpdate {
+                        state: t.from.state(),
+                        action: t.action,
+                        error: error / n,
+                    })
+                    .map(|q_res| Response { q_res, error })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.sync_target();
+
+        Ok(responses)
+    }
+}
+
+impl<'m, S, Q> Handler<&'m Trajectory<S, usize>> for SoftQLearning<Q>
+where
+    Q: Enumerable<(&'m S,)> + Handler<StateActionUpdate<&'m S, usize, f64>> + Parameterised,
+    <Q as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = Vec<Response<Q::Response>>;
+    type Error = Q::Error;
+
+    fn handle(&mut self, traj: &'m Trajectory<S, usize>) -> Result<Self::Response, Self::Error> {
+        traj.iter()
+            .map(|t| {
+                let error = self.td_error(&t);
+                let q_res = self.q_func.handle(StateActionUpdate {
+                    state: *t.from.state(),
+                    action: *t.action,
+                    error,
+                })?;
+
+                self.sync_target();
+
+                Ok(Response { q_res, error })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_limits() {
+        let values = [1.0, 3.0, 2.0];
+
+        for &tau in &[1.0, 1e-1, 1e-3] {
+            let bound = tau * 3.0f64.ln() + 1e-12;
+            let lse = Backup::LogSumExp.apply(&values, tau);
+            let mm = Backup::Mellowmax.apply(&values, tau);
+
+            assert!(lse >= 3.0 && lse - 3.0 <= bound);
+            assert!(mm <= 3.0 && 3.0 - mm <= bound);
+        }
+
+        assert!((Backup::LogSumExp.apply(&values, 1e-3) - 3.0).abs() < 1e-10);
+        assert!((Backup::Mellowmax.apply(&values, 1e-3) - 3.0).abs() < 2e-3);
+    }
+
+    #[test]
+    fn test_mellowmax_equal_values() {
+        for &tau in &[1e-2, 1.0, 10.0] {
+            assert!((Backup::Mellowmax.apply(&[2.5; 4], tau) - 2.5).abs() < 1e-12);
+        }
+    }
+}
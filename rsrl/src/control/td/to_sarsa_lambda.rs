@@ -18,6 +18,11 @@ use std::f64;
 /// Sutton, R. S. (2016). True online temporal-difference learning. Journal of
 /// Machine Learning Research, 17(145), 1-40.](https://arxiv.org/pdf/1512.04087.pdf)
 #[derive(Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct TOSARSALambda<B: Space, P, T> {
     pub basis: B,
     #[weights] pub theta: Array1<f64>,
@@ -0,0 +1,323 @@
+//! Trust region policy optimisation.
+use crate::{
+    control::ac::gae,
+    domains::{Batch, Trajectory, Transition},
+    fa::StateUpdate,
+    policies::DifferentiablePolicy,
+    Function,
+    Handler,
+};
+use ndarray::Array1;
+
+/// Approximately solve `Ax = b` for symmetric positive-definite `A` using at
+/// most `n_iter` iterations of the conjugate gradient method; `A` is only
+/// accessed through the matrix-vector product `avp`.
+pub fn conjugate_gradient<F>(avp: F, b: &Array1<f64>, n_iter: usize, tol: f64) -> Array1<f64>
+where F: Fn(&Array1<f64>) -> Array1<f64> {
+    let mut x = Array1::zeros(b.len());
+    let mut r = b.clone();
+    let mut p = b.clone();
+    let mut rr = r.dot(&r);
+
+    for _ in 0..n_iter {
+        if rr < tol {
+            break;
+        }
+
+        let ap = avp(&p);
+        let alpha = rr / p.dot(&ap);
+
+        x.scaled_add(alpha, &p);
+        r.scaled_add(-alpha, &ap);
+
+        let rr_new = r.dot(&r);
+
+        p = &r + &(p * (rr_new / rr));
+        rr = rr_new;
+    }
+
+    x
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Response {
+    /// Generalised advantage estimates for each transition in the batch.
+    pub advantages: Vec<f64>,
+
+    /// Sampled KL divergence between the old and updated policies.
+    pub kl: f64,
+
+    /// Fraction of the full natural gradient step that was accepted.
+    pub step_fraction: f64,
+}
+
+/// Trust region policy optimisation with a value critic.
+///
+/// The natural gradient direction is found by conjugate gradient, using
+/// Fisher-vector products estimated from the `grad_log` samples in the batch.
+/// The step is scaled to the boundary of the KL trust region, `max_kl`, and
+/// then shrunk by backtracking until the sampled KL divergence is within the
+/// region and the surrogate objective improves. Advantages are computed with
+/// GAE(γ, λ), after which the critic is regressed onto the λ-returns.
+///
+/// # References
+/// - Schulman, J., Levine, S., Abbeel, P., Jordan, M., Moritz, P. (2015).
+/// Trust region policy optimization. In Proceedings of ICML, pp. 1889-1897.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct TRPO<V, P> {
+    pub v_func: V,
+    pub policy: P,
+
+    pub gamma: f64,
+    pub lambda: f64,
+    pub max_kl: f64,
+
+    pub damping: f64,
+    pub cg_iters: usize,
+    pub backtrack_iters: usize,
+    pub backtrack_ratio: f64,
+}
+
+impl<V, P> TRPO<V, P> {
+    pub fn new(v_func: V, policy: P, gamma: f64, lambda: f64, max_kl: f64) -> Self {
+        TRPO {
+            v_func,
+            policy,
+
+            gamma,
+            lambda,
+            max_kl,
+
+            damping: 0.1,
+            cg_iters: 10,
+            backtrack_iters: 10,
+            backtrack_ratio: 0.5,
+        }
+    }
+
+    /// Return the mean surrogate objective and KL divergence from the old
+    /// policy over the batch.
+    fn evaluate_step<'m, S>(
+        &self,
+        transitions: &[Transition<&'m S, &'m P::Action>],
+        advantages: &[f64],
+        log_probs: &[f64],
+    ) -> (f64, f64)
+    where
+        P: DifferentiablePolicy<&'m S>,
+    {
+        let n = transitions.len().max(1) as f64;
+        let (surrogate, kl) = transitions
+            .iter()
+            .zip(advantages.iter().zip(log_probs.iter()))
+            .fold((0.0, 0.0), |(acc_s, acc_kl), (t, (adv, lp))| {
+                let new_lp = self.policy.evaluate((*t.from.state(), t.action)).ln();
+
+                (acc_s + (new_lp - lp).exp() * adv, acc_kl + lp - new_lp)
+            });
+
+        (surrogate / n, kl / n)
+    }
+
+    fn update<'m, S>(
+        &mut self,
+        transitions: &[Transition<&'m S, &'m P::Action>],
+    ) -> Result<Response, V::Error>
+    where
+        V: Function<(&'m S,), Output = f64> + Handler<StateUpdate<&'m S, f64>>,
+        P: DifferentiablePolicy<&'m S>,
+    {
+        let n = transitions.len().max(1) as f64;
+        let advantages = gae(&self.v_func, transitions, self.gamma, self.lambda);
+
+        let log_probs: Vec<f64> = transitions
+            .iter()
+            .map(|t| self.policy.evaluate((*t.from.state(), t.action)).ln())
+            .collect();
+        let grads: Vec<Array1<f64>> = transitions
+            .iter()
+            .map(|t| self.policy.grad_log((*t.from.state(), t.action)).iter().cloned().collect())
+            .collect();
+
+        let mut g = Array1::zeros(self.policy.n_weights());
+
+        for (gl, adv) in grads.iter().zip(advantages.iter()) {
+            g.scaled_add(adv / n, gl);
+        }
+
+        let fvp = |v: &Array1<f64>| {
+            let mut out = v * self.damping;
+
+            for gl in grads.iter() {
+                out.scaled_add(gl.dot(v) / n, gl);
+            }
+
+            out
+        };
+
+        let direction = conjugate_gradient(fvp, &g, self.cg_iters, 1e-10);
+        let shs = direction.dot(&fvp(&direction));
+
+        let mut step_fraction = 0.0;
+
+        if shs > 0.0 {
+            let full_step = direction * (2.0 * self.max_kl / shs).sqrt();
+            let theta = self.policy.weights();
+            let (surrogate, _) = self.evaluate_step(transitions, &advantages, &log_probs);
+
+            let mut fraction = 1.0;
+
+            for _ in 0..self.backtrack_iters {
+                self.policy
+                    .weights_view_mut()
+                    .iter_mut()
+                    .zip(theta.iter().zip(full_step.iter()))
+                    .for_each(|(w, (w0, dw))| *w = w0 + fraction * dw);
+
+                let (new_surrogate, kl) =
+                    self.evaluate_step(transitions, &advantages, &log_probs);
+
+                if kl <= self.max_kl && new_surrogate > surrogate {
+                    step_fraction = fraction;
+
+                    break;
+                }
+
+                fraction *= self.backtrack_ratio;
+            }
+
+            if step_fraction == 0.0 {
+                self.policy.weights_view_mut().assign(&theta);
+            }
+        }
+
+        let (_, kl) = self.evaluate_step(transitions, &advantages, &log_probs);
+
+        for (t, adv) in transitions.iter().zip(advantages.iter()) {
+            let s = *t.from.state();
+
+            self.v_func.handle(StateUpdate {
+                state: s,
+                error: *adv,
+            })?;
+        }
+
+        Ok(Response {
+            advantages,
+            kl,
+            step_fraction,
+        })
+    }
+}
+
+impl<'m, S, V, P> Handler<&'m Batch<S, P::Action>> for TRPO<V, P>
+where
+    V: Function<(&'m S,), Output = f64> + Handler<StateUpdate<&'m S, f64>>,
+    P: DifferentiablePolicy<&'m S>,
+{
+    type Response = Response;
+    type Error = V::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, P::Action>) -> Result<Response, Self::Error> {
+        let transitions: Vec<_> = batch.iter().map(|t| t.borrowed()).collect();
+
+        self.update(&transitions)
+    }
+}
+
+impl<'m, S, V, P> Handler<&'m Trajectory<S, P::Action>> for TRPO<V, P>
+where
+    V: Function<(&'m S,), Output = f64> + Handler<StateUpdate<&'m S, f64>>,
+    P: DifferentiablePolicy<&'m S>,
+{
+    type Response = Response;
+    type Error = V::Error;
+
+    fn handle(&mut self, traj: &'m Trajectory<S, P::Action>) -> Result<Response, Self::Error> {
+        let transitions: Vec<Transition<&'m S, &'m P::Action>> = traj.iter().collect();
+
+        self.update(&transitions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::Observation,
+        fa::tabular::Table,
+        params::Parameterised,
+        policies::Softmax,
+    };
+    use ndarray::Array2;
+
+    #[test]
+    fn test_conjugate_gradient() {
+        // A = [[4, 1], [1, 3]], b = [1, 2] => x = [1/11, 7/11].
+        let avp = |v: &Array1<f64>| Array1::from(vec![4.0 * v[0] + v[1], v[0] + 3.0 * v[1]]);
+        let x = conjugate_gradient(avp, &Array1::from(vec![1.0, 2.0]), 10, 1e-12);
+
+        assert!((x[0] - 1.0 / 11.0).abs() < 1e-7);
+        assert!((x[1] - 7.0 / 11.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_softmax_trust_region() {
+        let v_func = Table::dense(Array1::zeros(2));
+        let weights =
+            Array2::from_shape_vec((2, 3), vec![0.5, -1.0, 0.0, 0.0, 2.0, -0.5]).unwrap();
+        let policy = Softmax::standard(Table::dense(weights));
+        let mut agent = TRPO::new(v_func, policy, 0.9, 0.95, 0.01);
+
+        let traj = Trajectory {
+            start: Observation::Full(0usize),
+            steps: vec![
+                (Observation::Full(1), 2usize, 1.0),
+                (Observation::Terminal(0), 0, -1.0),
+            ],
+        };
+        let transitions: Vec<Transition<&usize, &usize>> = traj.iter().collect();
+        let log_probs: Vec<f64> = transitions
+            .iter()
+            .map(|t| agent.policy.evaluate((*t.from.state(), t.action)).ln())
+            .collect();
+
+        // Before any step every ratio is one and the sampled KL vanishes.
+        let (surrogate, kl) = agent.evaluate_step(&transitions, &[1.0, -2.0], &log_probs);
+
+        assert!((surrogate + 0.5).abs() < 1e-10);
+        assert!(kl.abs() < 1e-10);
+
+        let res = agent.handle(&traj).unwrap();
+
+        assert!(res.step_fraction > 0.0);
+        assert!(res.kl.is_finite() && res.kl <= 0.01);
+        assert!(agent.policy.weights_view().iter().all(|w| w.is_finite()));
+    }
+
+    #[test]
+    fn test_empty_batch() {
+        let v_func = Table::dense(Array1::zeros(2));
+        let policy = Softmax::standard(Table::dense(Array2::zeros((2, 3))));
+        let mut agent = TRPO::new(v_func, policy, 0.9, 0.95, 0.01);
+
+        let transitions: Vec<Transition<&usize, &usize>> = vec![];
+
+        assert_eq!(agent.evaluate_step(&transitions, &[], &[]), (0.0, 0.0));
+
+        let res = agent.update(&transitions).unwrap();
+
+        assert_eq!(res.kl, 0.0);
+        assert_eq!(res.step_fraction, 0.0);
+    }
+}
@@ -43,6 +43,216 @@ impl<T> Clone for Shared<T> {
     fn clone(&self) -> Shared<T> { Shared(self.0.clone()) }
 }
 
+#[cfg(feature = "serde")]
+pub use self::sharing::preserve_sharing;
+
+#[cfg(feature = "serde")]
+mod sharing {
+    use super::{make_shared, Shared};
+    use serde_crate::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use std::{
+        any::Any,
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        rc::Rc,
+    };
+
+    thread_local! {
+        static DEPTH: Cell<usize> = Cell::new(0);
+        static IDS: RefCell<HashMap<usize, usize>> = RefCell::new(HashMap::new());
+        static PTRS: RefCell<HashMap<usize, Rc<dyn Any>>> = RefCell::new(HashMap::new());
+    }
+
+    struct Scope;
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            let depth = DEPTH.with(|d| {
+                d.set(d.get() - 1);
+                d.get()
+            });
+
+            if depth == 0 {
+                IDS.with(|ids| ids.borrow_mut().clear());
+                PTRS.with(|ptrs| ptrs.borrow_mut().clear());
+            }
+        }
+    }
+
+    /// Run `f` with sharing between `Shared` instances preserved through
+    /// serde.
+    ///
+    /// Within this scope each distinct `Shared` value is serialised in full
+    /// only once; later references to the same value are written as an id
+    /// and resolved back to a single `Rc` on deserialisation. Outside of it,
+    /// every `Shared` is serialised (and deserialised) as an independent
+    /// copy.
+    pub fn preserve_sharing<R>(f: impl FnOnce() -> R) -> R {
+        DEPTH.with(|d| d.set(d.get() + 1));
+
+        let _scope = Scope;
+
+        f()
+    }
+
+    fn is_preserving() -> bool { DEPTH.with(|d| d.get() > 0) }
+
+    #[derive(Serialize)]
+    #[serde(crate = "serde_crate", rename = "Shared")]
+    struct SharedRef<'a, T> {
+        id: Option<usize>,
+        value: Option<&'a T>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(crate = "serde_crate", rename = "Shared")]
+    struct SharedValue<T> {
+        id: Option<usize>,
+        value: Option<T>,
+    }
+
+    impl<T: Serialize> Serialize for Shared<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let value = self.borrow();
+
+            if !is_preserving() {
+                return SharedRef {
+                    id: None,
+                    value: Some(&*value),
+                }
+                .serialize(serializer);
+            }
+
+            let key = self.as_ptr() as usize;
+            let (id, is_first) = IDS.with(|ids| {
+                let mut ids = ids.borrow_mut();
+                let n = ids.len();
+
+                match ids.get(&key) {
+                    Some(&id) => (id, false),
+                    None => {
+                        ids.insert(key, n);
+
+                        (n, true)
+                    },
+                }
+            });
+
+            SharedRef {
+                id: Some(id),
+                value: if is_first { Some(&*value) } else { None },
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de> + 'static> Deserialize<'de> for Shared<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let SharedValue { id, value } = SharedValue::<T>::deserialize(deserializer)?;
+
+            match (id, value) {
+                (id, Some(value)) => {
+                    let shared = make_shared(value);
+
+                    if let (Some(id), true) = (id, is_preserving()) {
+                        let ptr: Rc<dyn Any> = shared.0.clone();
+
+                        PTRS.with(|ptrs| ptrs.borrow_mut().insert(id, ptr));
+                    }
+
+                    Ok(shared)
+                },
+                (Some(id), None) => PTRS
+                    .with(|ptrs| ptrs.borrow().get(&id).cloned())
+                    .and_then(|ptr| ptr.downcast::<RefCell<T>>().ok())
+                    .map(Shared)
+                    .ok_or_else(|| {
+                        de::Error::custom(format!("unresolved reference to shared value {}", id))
+                    }),
+                (None, None) => Err(de::Error::custom("missing shared value")),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use serde_test::{assert_de_tokens, assert_ser_tokens, Token};
+
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(crate = "serde_crate")]
+        struct Pair(Shared<f64>, Shared<f64>);
+
+        impl PartialEq for Pair {
+            fn eq(&self, other: &Pair) -> bool {
+                let is_shared = |p: &Pair| Rc::ptr_eq(&(p.0).0, &(p.1).0);
+
+                *self.0 == *other.0 && *self.1 == *other.1 && is_shared(self) == is_shared(other)
+            }
+        }
+
+        const SHARED: Token = Token::Struct {
+            name: "Shared",
+            len: 2,
+        };
+        const PAIR: Token = Token::TupleStruct {
+            name: "Pair",
+            len: 2,
+        };
+
+        #[test]
+        fn test_independent_copies() {
+            let x = make_shared(1.0);
+
+            assert_ser_tokens(&Pair(x.clone(), x), &[
+                PAIR,
+                SHARED,
+                Token::Str("id"),
+                Token::None,
+                Token::Str("value"),
+                Token::Some,
+                Token::F64(1.0),
+                Token::StructEnd,
+                SHARED,
+                Token::Str("id"),
+                Token::None,
+                Token::Str("value"),
+                Token::Some,
+                Token::F64(1.0),
+                Token::StructEnd,
+                Token::TupleStructEnd,
+            ]);
+        }
+
+        #[test]
+        fn test_preserve_sharing() {
+            let x = make_shared(1.0);
+            let tokens = [
+                PAIR,
+                SHARED,
+                Token::Str("id"),
+                Token::Some,
+                Token::U64(0),
+                Token::Str("value"),
+                Token::Some,
+                Token::F64(1.0),
+                Token::StructEnd,
+                SHARED,
+                Token::Str("id"),
+                Token::Some,
+                Token::U64(0),
+                Token::Str("value"),
+                Token::None,
+                Token::StructEnd,
+                Token::TupleStructEnd,
+            ];
+
+            preserve_sharing(|| assert_ser_tokens(&Pair(x.clone(), x.clone()), &tokens));
+            preserve_sharing(|| assert_de_tokens(&Pair(x.clone(), x), &tokens));
+        }
+    }
+}
+
 pub type OutputOf<F, S> = <F as Function<S>>::Output;
 
 // TODO: When the ABI drops we can basically implement this like the (curently unstable) Fn traits.
@@ -0,0 +1,220 @@
+//! Visitation counters for count-based exploration.
+use crate::fa::linear::{basis::Basis, Features};
+use ndarray::{Array2, Axis};
+use rand::thread_rng;
+use rand_distr::{Distribution, StandardNormal};
+use std::{
+    borrow::Borrow,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+/// Counter of the number of visits to each state-action pair.
+pub trait Counts<S> {
+    /// Return the number of visits to the pair `(s, a)`.
+    fn count(&self, s: S, a: usize) -> usize;
+
+    /// Return the number of visits to `s`, summed over all actions.
+    fn total(&self, s: S) -> usize;
+
+    /// Register a visit to the pair `(s, a)`.
+    fn increment(&mut self, s: S, a: usize);
+}
+
+/// Exact visitation counts over a finite set of states.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct TabularCounts(Array2<usize>);
+
+impl TabularCounts {
+    pub fn new(n_states: usize, n_actions: usize) -> Self {
+        TabularCounts(Array2::zeros((n_states, n_actions)))
+    }
+}
+
+impl<S: Borrow<usize>> Counts<S> for TabularCounts {
+    fn count(&self, s: S, a: usize) -> usize { self.0[(*s.borrow(), a)] }
+
+    fn total(&self, s: S) -> usize { self.0.index_axis(Axis(0), *s.borrow()).sum() }
+
+    fn increment(&mut self, s: S, a: usize) { self.0[(*s.borrow(), a)] += 1; }
+}
+
+fn count_bucket(buckets: &HashMap<u64, Vec<usize>>, key: u64, a: usize) -> usize {
+    buckets.get(&key).map_or(0, |cs| cs[a])
+}
+
+fn total_bucket(buckets: &HashMap<u64, Vec<usize>>, key: u64) -> usize {
+    buckets.get(&key).map_or(0, |cs| cs.iter().sum())
+}
+
+fn increment_bucket(buckets: &mut HashMap<u64, Vec<usize>>, n_actions: usize, key: u64, a: usize) {
+    buckets.entry(key).or_insert_with(|| vec![0; n_actions])[a] += 1;
+}
+
+/// Visitation counts over states that can be hashed, e.g. discrete states with
+/// an unknown or unbounded range.
+///
+/// Only states that have been visited are stored. Distinct states are counted
+/// separately up to collisions of their 64-bit hashes.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct HashedCounts {
+    n_actions: usize,
+    buckets: HashMap<u64, Vec<usize>>,
+}
+
+impl HashedCounts {
+    pub fn new(n_actions: usize) -> Self {
+        HashedCounts {
+            n_actions,
+            buckets: HashMap::new(),
+        }
+    }
+
+    fn key<S: Hash>(s: S) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        s.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<S: Hash> Counts<S> for HashedCounts {
+    fn count(&self, s: S, a: usize) -> usize { count_bucket(&self.buckets, Self::key(s), a) }
+
+    fn total(&self, s: S) -> usize { total_bucket(&self.buckets, Self::key(s)) }
+
+    fn increment(&mut self, s: S, a: usize) {
+        increment_bucket(&mut self.buckets, self.n_actions, Self::key(s), a)
+    }
+}
+
+/// Visitation pseudo-counts over continuous states using locality-sensitive
+/// hashing.
+///
+/// Each state is projected onto `basis`, and the resulting features are
+/// mapped to a `k`-bit code given by the signs of a fixed Gaussian random
+/// projection. States with the same code share a count; larger values of `k`
+/// yield finer discretisations.
+///
+/// # References
+/// - Tang, H., et al. (2017). #Exploration: A study of count-based exploration
+/// for deep reinforcement learning. In Proceedings of NIPS, pp. 2753-2762.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct SimHashCounts<B> {
+    pub basis: B,
+
+    n_actions: usize,
+    projection: Array2<f64>,
+    buckets: HashMap<u64, Vec<usize>>,
+}
+
+impl<B: spaces::Space> SimHashCounts<B> {
+    pub fn new(basis: B, k: usize, n_actions: usize) -> Self {
+        if k == 0 || k > 64 {
+            panic!("SimHashCounts requires a code length in the range [1, 64].");
+        }
+
+        let mut rng = thread_rng();
+        let n_features: usize = basis.dim().into();
+        let projection =
+            Array2::from_shape_fn((k, n_features), |_| StandardNormal.sample(&mut rng));
+
+        SimHashCounts {
+            basis,
+
+            n_actions,
+            projection,
+            buckets: HashMap::new(),
+        }
+    }
+
+    fn key<S>(&self, s: S) -> u64
+    where B: Basis<S, Value = Features> {
+        let phi = self.basis.project(s).unwrap().into_dense();
+
+        self.projection
+            .outer_iter()
+            .enumerate()
+            .fold(0, |code, (i, row)| {
+                let proj = row.iter().zip(phi.iter()).fold(0.0, |acc, (a, b)| acc + a * b);
+
+                if proj > 0.0 {
+                    code | (1 << i)
+                } else {
+                    code
+                }
+            })
+    }
+}
+
+impl<S, B> Counts<S> for SimHashCounts<B>
+where B: spaces::Space + Basis<S, Value = Features>
+{
+    fn count(&self, s: S, a: usize) -> usize { count_bucket(&self.buckets, self.key(s), a) }
+
+    fn total(&self, s: S) -> usize { total_bucket(&self.buckets, self.key(s)) }
+
+    fn increment(&mut self, s: S, a: usize) {
+        let key = self.key(s);
+
+        increment_bucket(&mut self.buckets, self.n_actions, key, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fa::linear::basis::Polynomial;
+
+    #[test]
+    fn test_tabular() {
+        let mut counts = TabularCounts::new(2, 3);
+
+        counts.increment(1, 2);
+        counts.increment(1, 2);
+        counts.increment(1, 0);
+
+        assert_eq!(counts.count(1, 2), 2);
+        assert_eq!(counts.count(0, 2), 0);
+        assert_eq!(counts.total(1), 3);
+    }
+
+    #[test]
+    fn test_hashed() {
+        let mut counts = HashedCounts::new(2);
+
+        counts.increment("a", 1);
+        counts.increment("b", 0);
+        counts.increment("a", 1);
+
+        assert_eq!(counts.count("a", 1), 2);
+        assert_eq!(counts.count("c", 1), 0);
+        assert_eq!(counts.total("b"), 1);
+    }
+
+    #[test]
+    fn test_simhash() {
+        let mut counts = SimHashCounts::new(Polynomial::new(2, 1), 16, 2);
+
+        counts.increment(&vec![1.0, 0.0], 0);
+
+        // Codes are invariant to positive scaling of the features.
+        assert_eq!(counts.count(&vec![2.0, 0.0], 0), 1);
+        assert_eq!(counts.count(&vec![-1.0, 0.0], 0), 0);
+    }
+}
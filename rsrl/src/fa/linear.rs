@@ -5,6 +5,7 @@ use crate::{
     Enumerable,
     Function,
     Handler,
+    Shared,
 };
 use ndarray::{Array1, ArrayBase, Axis, DataMut, Dimension, Ix1, IntoDimension};
 
@@ -107,6 +108,68 @@ pub mod basis {
 
 type Jacobian = Columnar<Features>;
 
+/// Linear function approximators that expose the basis used to generate their
+/// features, e.g. for use by least-squares methods.
+pub trait LinearBasis {
+    type Basis;
+
+    /// Return a reference to the basis.
+    fn basis(&self) -> &Self::Basis;
+}
+
+impl<B, W, O> LinearBasis for LFA<B, W, O> {
+    type Basis = B;
+
+    fn basis(&self) -> &B { &self.basis }
+}
+
+impl<T: LinearBasis> LinearBasis for Shared<T> {
+    type Basis = T::Basis;
+
+    fn basis(&self) -> &T::Basis { (**self).basis() }
+}
+
+/// Construct a `ScalarLFA` whose output is initialised optimistically to
+/// `value`.
+///
+/// Every weight is set to `value / activation`, where `activation` is the sum
+/// of the features produced by `basis` for any input; e.g. the number of
+/// tilings of a tile coding, or `1` for a normalised basis.
+pub fn optimistic_scalar<B, O>(
+    basis: B,
+    optimiser: O,
+    value: f64,
+    activation: f64,
+) -> ScalarLFA<B, O>
+where
+    B: spaces::Space,
+{
+    let mut lfa = LFA::scalar(basis, optimiser);
+
+    lfa.weights.fill(value / activation);
+
+    lfa
+}
+
+/// Construct a `VectorLFA` whose outputs are all initialised optimistically to
+/// `value`; see `optimistic_scalar`.
+pub fn optimistic_vector<B, O>(
+    basis: B,
+    optimiser: O,
+    n_outputs: usize,
+    value: f64,
+    activation: f64,
+) -> VectorLFA<B, O>
+where
+    B: spaces::Space,
+{
+    let mut lfa = LFA::vector(basis, optimiser, n_outputs);
+
+    lfa.weights.fill(value / activation);
+
+    lfa
+}
+
 impl Buffer for Features {
     type Dim = Ix1;
 
@@ -389,3 +452,43 @@ where
         self.update_index(msg.state, *msg.action.borrow(), msg.error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fa::linear::{basis::Closure, optim::SGD};
+
+    fn one_hot(s: &usize) -> Result<Features> {
+        let mut phi = Array1::zeros(3);
+        phi[*s] = 1.0;
+
+        Ok(Features::Dense(phi))
+    }
+
+    #[test]
+    fn test_optimistic_scalar() {
+        let lfa = optimistic_scalar(Closure::new(3, one_hot), SGD(1.0), 5.0, 1.0);
+
+        for s in 0..3 {
+            assert!((Function::evaluate(&lfa, (&s,)) - 5.0).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_optimistic_vector() {
+        let lfa = optimistic_vector(Closure::new(3, one_hot), SGD(1.0), 2, 5.0, 1.0);
+
+        for s in 0..3 {
+            assert!(Function::evaluate(&lfa, (&s,)).iter().all(|v| (v - 5.0).abs() < 1e-10));
+        }
+    }
+
+    #[test]
+    fn test_optimistic_activation() {
+        // Two active features per input, as in a tile coding with two tilings.
+        let basis = Closure::new(2, |_: &usize| Ok(Features::Dense(Array1::ones(2))));
+        let lfa = optimistic_scalar(basis, SGD(1.0), 5.0, 2.0);
+
+        assert!((Function::evaluate(&lfa, (&0,)) - 5.0).abs() < 1e-10);
+    }
+}
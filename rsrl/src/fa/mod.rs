@@ -48,8 +48,13 @@ pub struct ScaledGradientUpdate<J: Buffer> {
 
 pub mod linear;
 pub mod tabular;
+pub mod neural;
 
 pub mod transforms;
+pub mod step_size;
 
 mod composition;
 pub use self::composition::Composition;
+
+mod target;
+pub use self::target::{TargetFunction, TargetSync};
@@ -0,0 +1,504 @@
+//! Feed-forward neural network function approximators.
+use crate::{
+    fa::{
+        transforms::{self, Transform},
+        GradientUpdate,
+        ScaledGradientUpdate,
+        StateActionUpdate,
+        StateUpdate,
+    },
+    optim::GradientOptimiser,
+    params::*,
+    Differentiable,
+    Enumerable,
+    Function,
+    Handler,
+};
+use ndarray::{Array1, Array2, Axis, Ix2};
+use rand::Rng;
+use std::marker::PhantomData;
+
+/// Activation functions available to each layer of an `MLP`.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum Activation {
+    Identity,
+    Tanh,
+    Softplus,
+    Logistic(transforms::Logistic),
+}
+
+impl Transform<f64> for Activation {
+    type Output = f64;
+
+    fn transform(&self, x: f64) -> f64 {
+        match self {
+            Activation::Identity => transforms::Identity.transform(x),
+            Activation::Tanh => transforms::Tanh.transform(x),
+            Activation::Softplus => transforms::Softplus.transform(x),
+            Activation::Logistic(ref l) => l.transform(x),
+        }
+    }
+
+    fn grad(&self, x: f64) -> f64 {
+        match self {
+            Activation::Identity => transforms::Identity.grad(x),
+            Activation::Tanh => transforms::Tanh.grad(x),
+            Activation::Softplus => transforms::Softplus.grad(x),
+            Activation::Logistic(ref l) => l.grad(x),
+        }
+    }
+
+    fn grad_scaled(&self, x: f64, error: f64) -> f64 { self.grad(x) * error }
+}
+
+/// Marker for networks with a single output unit.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Scalar;
+
+/// Marker for networks with one output unit per action.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Vector;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+struct Layer {
+    n_inputs: usize,
+    n_outputs: usize,
+    offset: usize,
+    activation: Activation,
+}
+
+impl Layer {
+    #[inline]
+    fn weight_index(&self, o: usize, i: usize) -> usize { self.offset + o * self.n_inputs + i }
+
+    #[inline]
+    fn bias_index(&self, o: usize) -> usize { self.offset + self.n_outputs * self.n_inputs + o }
+
+    fn n_params(&self) -> usize { self.n_outputs * (self.n_inputs + 1) }
+}
+
+/// Multilayer perceptron trained by backpropagation.
+///
+/// All weights and biases are stored in a single `(n_params x 1)` column so
+/// that the network can be used anywhere a `Parameterised` function is
+/// expected. Inputs are any type that can be viewed as a slice of `f64`.
+/// `StateUpdate` and `StateActionUpdate` messages are applied through the
+/// given `GradientOptimiser`, whereas `GradientUpdate` and
+/// `ScaledGradientUpdate` are added to the weights directly.
+///
+/// # References
+/// - Rumelhart, D. E., Hinton, G. E., & Williams, R. J. (1986). Learning
+/// representations by back-propagating errors. Nature, 323(6088), 533-536.
+/// - Glorot, X., & Bengio, Y. (2010). Understanding the difficulty of training
+/// deep feedforward neural networks. In Proceedings of the 13th International
+/// Conference on Artificial Intelligence and Statistics, pp. 249-256.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct MLP<K, O> {
+    layers: Vec<Layer>,
+    weights: Array2<f64>,
+
+    pub optimiser: O,
+
+    kind: PhantomData<K>,
+}
+
+pub type ScalarMLP<O> = MLP<Scalar, O>;
+pub type VectorMLP<O> = MLP<Vector, O>;
+
+impl<K, O> MLP<K, O> {
+    fn build<R>(rng: &mut R, n_inputs: usize, layers: &[(usize, Activation)], optimiser: O) -> Self
+    where R: Rng + ?Sized {
+        let mut offset = 0;
+        let mut n_in = n_inputs;
+
+        let layers: Vec<Layer> = layers
+            .iter()
+            .map(|&(n_outputs, ref activation)| {
+                let layer = Layer {
+                    n_inputs: n_in,
+                    n_outputs,
+                    offset,
+                    activation: activation.clone(),
+                };
+
+                offset += layer.n_params();
+                n_in = n_outputs;
+
+                layer
+            })
+            .collect();
+
+        let mut weights = Array2::zeros((offset, 1));
+
+        for layer in layers.iter() {
+            let limit = (6.0 / (layer.n_inputs + layer.n_outputs) as f64).sqrt();
+
+            for o in 0..layer.n_outputs {
+                for i in 0..layer.n_inputs {
+                    weights[(layer.weight_index(o, i), 0)] = rng.gen_range(-limit, limit);
+                }
+            }
+        }
+
+        MLP {
+            layers,
+            weights,
+
+            optimiser,
+
+            kind: PhantomData,
+        }
+    }
+
+    /// Return the number of inputs expected by the network.
+    pub fn n_inputs(&self) -> usize { self.layers[0].n_inputs }
+
+    /// Return the number of output units of the network.
+    pub fn n_outputs(&self) -> usize { self.layers[self.layers.len() - 1].n_outputs }
+
+    /// Compute the pre-activations and activations of every layer.
+    fn forward(&self, input: &[f64]) -> (Vec<Array1<f64>>, Vec<Array1<f64>>) {
+        let mut zs = Vec::with_capacity(self.layers.len());
+        let mut activations = Vec::with_capacity(self.layers.len() + 1);
+
+        activations.push(Array1::from(input.to_vec()));
+
+        for layer in self.layers.iter() {
+            let a = &activations[activations.len() - 1];
+            let z = Array1::from_shape_fn(layer.n_outputs, |o| {
+                (0..layer.n_inputs).fold(self.weights[(layer.bias_index(o), 0)], |acc, i| {
+                    acc + self.weights[(layer.weight_index(o, i), 0)] * a[i]
+                })
+            });
+
+            activations.push(z.mapv(|x| layer.activation.transform(x)));
+            zs.push(z);
+        }
+
+        (zs, activations)
+    }
+
+    /// Evaluate the network outputs for a given `input`.
+    pub fn outputs(&self, input: &[f64]) -> Array1<f64> {
+        let (_, mut activations) = self.forward(input);
+
+        activations.pop().unwrap()
+    }
+
+    /// Backpropagate the output `errors` to obtain the gradient of
+    /// `errors . outputs` with respect to the parameters.
+    pub fn backward(&self, input: &[f64], errors: &Array1<f64>) -> Array2<f64> {
+        let (zs, activations) = self.forward(input);
+        let mut grad = Array2::zeros(self.weights.dim());
+
+        let last = &self.layers[self.layers.len() - 1];
+        let mut delta: Array1<f64> = Array1::from_shape_fn(last.n_outputs, |o| {
+            errors[o] * last.activation.grad(zs[zs.len() - 1][o])
+        });
+
+        for (l, layer) in self.layers.iter().enumerate().rev() {
+            let a = &activations[l];
+
+            for o in 0..layer.n_outputs {
+                grad[(layer.bias_index(o), 0)] = delta[o];
+
+                for i in 0..layer.n_inputs {
+                    grad[(layer.weight_index(o, i), 0)] = delta[o] * a[i];
+                }
+            }
+
+            if l > 0 {
+                let prev = &self.layers[l - 1];
+
+                delta = Array1::from_shape_fn(layer.n_inputs, |i| {
+                    let back = (0..layer.n_outputs).fold(0.0, |acc, o| {
+                        acc + self.weights[(layer.weight_index(o, i), 0)] * delta[o]
+                    });
+
+                    back * prev.activation.grad(zs[l - 1][i])
+                });
+            }
+        }
+
+        grad
+    }
+
+    fn gradient_of(&self, input: &[f64], index: usize) -> Array2<f64> {
+        let mut errors = Array1::zeros(self.n_outputs());
+        errors[index] = 1.0;
+
+        self.backward(input, &errors)
+    }
+}
+
+impl<O: GradientOptimiser> MLP<Scalar, O> {
+    /// Construct a network with a single linear output unit.
+    pub fn scalar<R>(
+        rng: &mut R,
+        n_inputs: usize,
+        hidden: &[(usize, Activation)],
+        optimiser: O,
+    ) -> Self
+    where
+        R: Rng + ?Sized,
+    {
+        let layers: Vec<_> = hidden
+            .iter()
+            .cloned()
+            .chain(std::iter::once((1, Activation::Identity)))
+            .collect();
+
+        MLP::build(rng, n_inputs, &layers, optimiser)
+    }
+}
+
+impl<O: GradientOptimiser> MLP<Vector, O> {
+    /// Construct a network with `n_outputs` linear output units.
+    pub fn vector<R>(
+        rng: &mut R,
+        n_inputs: usize,
+        hidden: &[(usize, Activation)],
+        n_outputs: usize,
+        optimiser: O,
+    ) -> Self
+    where
+        R: Rng + ?Sized,
+    {
+        let layers: Vec<_> = hidden
+            .iter()
+            .cloned()
+            .chain(std::iter::once((n_outputs, Activation::Identity)))
+            .collect();
+
+        MLP::build(rng, n_inputs, &layers, optimiser)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// Common
+///////////////////////////////////////////////////////////////////////////////////////////////////
+impl<K, O> Parameterised for MLP<K, O> {
+    fn weights_view(&self) -> WeightsView { self.weights.view() }
+
+    fn weights_view_mut(&mut self) -> WeightsViewMut { self.weights.view_mut() }
+}
+
+impl<K, O, J> Handler<GradientUpdate<J>> for MLP<K, O>
+where J: Buffer<Dim = Ix2>
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: GradientUpdate<J>) -> Result<(), ()> {
+        msg.0.addto(&mut self.weights);
+
+        Ok(())
+    }
+}
+
+impl<K, O, J> Handler<ScaledGradientUpdate<J>> for MLP<K, O>
+where J: Buffer<Dim = Ix2>
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: ScaledGradientUpdate<J>) -> Result<(), ()> {
+        msg.jacobian.scaled_addto(msg.alpha, &mut self.weights);
+
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// Implement V(s)
+///////////////////////////////////////////////////////////////////////////////////////////////////
+impl<S: AsRef<[f64]>, O> Function<(S,)> for MLP<Scalar, O> {
+    type Output = f64;
+
+    fn evaluate(&self, (s,): (S,)) -> f64 { self.outputs(s.as_ref())[0] }
+}
+
+impl<S: AsRef<[f64]>, O> Differentiable<(S,)> for MLP<Scalar, O> {
+    type Jacobian = Array1<f64>;
+
+    fn grad(&self, (s,): (S,)) -> Array1<f64> {
+        self.gradient_of(s.as_ref(), 0).index_axis_move(Axis(1), 0)
+    }
+
+    fn grad_log(&self, (s,): (S,)) -> Array1<f64> {
+        let v = self.evaluate((s.as_ref(),));
+
+        self.grad((s,)) / v
+    }
+}
+
+impl<S: AsRef<[f64]>, O: GradientOptimiser> Handler<StateUpdate<S, f64>> for MLP<Scalar, O> {
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: StateUpdate<S, f64>) -> Result<(), ()> {
+        let grad = self.gradient_of(msg.state.as_ref(), 0);
+
+        self.optimiser.step(self.weights.view_mut(), grad.view(), msg.error);
+
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// Implement Q(s, a) with discrete a
+///////////////////////////////////////////////////////////////////////////////////////////////////
+impl<S: AsRef<[f64]>, O> Function<(S,)> for MLP<Vector, O> {
+    type Output = Vec<f64>;
+
+    fn evaluate(&self, (s,): (S,)) -> Vec<f64> { self.outputs(s.as_ref()).into_raw_vec() }
+}
+
+impl<S, A, O> Function<(S, A)> for MLP<Vector, O>
+where
+    S: AsRef<[f64]>,
+    A: std::borrow::Borrow<usize>,
+{
+    type Output = f64;
+
+    fn evaluate(&self, (s, a): (S, A)) -> f64 { self.outputs(s.as_ref())[*a.borrow()] }
+}
+
+impl<S: AsRef<[f64]>, O> Enumerable<(S,)> for MLP<Vector, O> {
+    fn len(&self, _: (S,)) -> usize { self.n_outputs() }
+}
+
+impl<S, A, O> Differentiable<(S, A)> for MLP<Vector, O>
+where
+    S: AsRef<[f64]>,
+    A: std::borrow::Borrow<usize>,
+{
+    type Jacobian = Array2<f64>;
+
+    fn grad(&self, (s, a): (S, A)) -> Array2<f64> { self.gradient_of(s.as_ref(), *a.borrow()) }
+
+    fn grad_log(&self, (s, a): (S, A)) -> Array2<f64> {
+        let v = self.evaluate((s.as_ref(), *a.borrow()));
+
+        self.grad((s, a)) / v
+    }
+}
+
+impl<S, E, O> Handler<StateUpdate<S, E>> for MLP<Vector, O>
+where
+    S: AsRef<[f64]>,
+    E: IntoIterator<Item = f64>,
+    O: GradientOptimiser,
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: StateUpdate<S, E>) -> Result<(), ()> {
+        let errors: Array1<f64> = msg.error.into_iter().collect();
+        let grad = self.backward(msg.state.as_ref(), &errors);
+
+        self.optimiser.step(self.weights.view_mut(), grad.view(), 1.0);
+
+        Ok(())
+    }
+}
+
+impl<S, A, O> Handler<StateActionUpdate<S, A, f64>> for MLP<Vector, O>
+where
+    S: AsRef<[f64]>,
+    A: std::borrow::Borrow<usize>,
+    O: GradientOptimiser,
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: StateActionUpdate<S, A, f64>) -> Result<(), ()> {
+        let grad = self.gradient_of(msg.state.as_ref(), *msg.action.borrow());
+
+        self.optimiser.step(self.weights.view_mut(), grad.view(), msg.error);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optim::PlainSGD;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_gradient() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let net = MLP::vector(
+            &mut rng,
+            2,
+            &[(4, Activation::Tanh), (3, Activation::Logistic(Default::default()))],
+            2,
+            PlainSGD(0.1),
+        );
+        let x = [0.3, -0.7];
+        let grad = net.grad((&x[..], 1usize));
+
+        // Compare against central finite differences:
+        for k in 0..net.n_weights() {
+            let mut plus = net.clone();
+            let mut minus = net.clone();
+
+            plus.weights[(k, 0)] += 1e-6;
+            minus.weights[(k, 0)] -= 1e-6;
+
+            let fd = (plus.evaluate((&x[..], 1usize)) - minus.evaluate((&x[..], 1usize))) / 2e-6;
+
+            assert!((fd - grad[(k, 0)]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_regression() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut net = MLP::scalar(&mut rng, 1, &[(8, Activation::Softplus)], PlainSGD(0.05));
+        let xs = [-1.0, -0.5, 0.0, 0.5, 1.0];
+
+        for _ in 0..5000 {
+            for &x in xs.iter() {
+                let error = x * x - net.evaluate((&[x][..],));
+
+                net.handle(StateUpdate {
+                    state: &[x][..],
+                    error,
+                })
+                .unwrap();
+            }
+        }
+
+        for &x in xs.iter() {
+            assert!((net.evaluate((&[x][..],)) - x * x).abs() < 0.05);
+        }
+    }
+}
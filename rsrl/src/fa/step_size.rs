@@ -0,0 +1,505 @@
+//! Adaptive step-size methods for linear function approximators.
+//!
+//! Each method can be used in two ways:
+//!
+//! 1. As an `lfa` optimiser, placed in the `optimiser` slot of an `LFA` in
+//!    lieu of `SGD(alpha)`. This covers learners which update through
+//!    `StateUpdate`/`StateActionUpdate` messages, such as `TD` and `QLearning`.
+//!    Note that a `VectorLFA` shares its step-sizes across the output columns
+//!    in this configuration.
+//! 2. Via the `Adaptive` wrapper, which holds one step-size per weight and
+//!    also intercepts the `ScaledGradientUpdate` messages sent by trace-based
+//!    learners such as `TDLambda` and `SARSALambda`. In the latter case the
+//!    learner's own `alpha` should be set to 1.
+use crate::{
+    fa::{linear, GradientUpdate, ScaledGradientUpdate, StateActionUpdate, StateUpdate},
+    params::{Buffer, Parameterised},
+    Differentiable,
+    Enumerable,
+    Function,
+    Handler,
+};
+use ndarray::{Array1, ArrayView1, ArrayViewMut1};
+use std::{cell::RefCell, ops::Index};
+
+/// Interface for adaptive step-size update rules.
+pub trait StepSize {
+    /// Apply an update of size `error` along the eligibility `trace`, adapting
+    /// the step-sizes using the current feature vector `phi`.
+    ///
+    /// For learners without eligibility traces, `trace` is equal to `phi`.
+    fn update(
+        &mut self,
+        weights: ArrayViewMut1<f64>,
+        phi: ArrayView1<f64>,
+        trace: ArrayView1<f64>,
+        error: f64,
+    );
+
+    /// Reset the internal state of the rule.
+    fn reset(&mut self);
+}
+
+macro_rules! impl_optimiser {
+    ($type:ty) => {
+        impl linear::optim::Optimiser<linear::Features> for $type {
+            fn step_scaled(
+                &mut self,
+                weights: &mut ArrayViewMut1<f64>,
+                features: &linear::Features,
+                error: f64,
+            ) -> linear::Result<()>
+            {
+                let phi = features.to_dense();
+
+                StepSize::update(self, weights.view_mut(), phi.view(), phi.view(), error);
+
+                Ok(())
+            }
+
+            fn reset(&mut self) { StepSize::reset(self) }
+        }
+    };
+}
+
+/// Incremental delta-bar-delta.
+///
+/// Maintains a log step-size per weight that is adapted by meta-gradient
+/// descent with meta step-size `theta`.
+///
+/// # References
+/// - Sutton, R. S. (1992). Adapting bias by gradient descent: An incremental
+/// version of delta-bar-delta. In Proceedings of the 10th National Conference
+/// on Artificial Intelligence, pp. 171-176.
+/// - Kearney, A., Veeriah, V., Travnik, J. B., Sutton, R. S., & Pilarski, P.
+/// M. (2018). TIDBD: Adapting Temporal-difference Step-sizes Through
+/// Stochastic Meta-descent. arXiv:1804.03334.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct IDBD {
+    pub theta: f64,
+
+    alpha_init: f64,
+    beta: Array1<f64>,
+    h: Array1<f64>,
+}
+
+impl IDBD {
+    pub fn new(n_weights: usize, alpha: f64, theta: f64) -> Self {
+        IDBD {
+            theta,
+
+            alpha_init: alpha,
+            beta: Array1::from_elem(n_weights, alpha.ln()),
+            h: Array1::zeros(n_weights),
+        }
+    }
+
+    /// Return the current step-sizes.
+    pub fn step_sizes(&self) -> Array1<f64> { self.beta.mapv(f64::exp) }
+}
+
+impl StepSize for IDBD {
+    fn update(
+        &mut self,
+        mut weights: ArrayViewMut1<f64>,
+        phi: ArrayView1<f64>,
+        trace: ArrayView1<f64>,
+        error: f64,
+    )
+    {
+        for i in 0..weights.len() {
+            let (x, e) = (phi[i], trace[i]);
+
+            self.beta[i] += self.theta * error * e * self.h[i];
+
+            let alpha = self.beta[i].exp();
+
+            weights[i] += alpha * error * e;
+            self.h[i] = self.h[i] * (1.0 - alpha * x * e).max(0.0) + alpha * error * e;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.beta.fill(self.alpha_init.ln());
+        self.h.fill(0.0);
+    }
+}
+
+impl_optimiser!(IDBD);
+
+/// Autostep: IDBD with normalised meta-updates and an effective step-size
+/// bound, making it robust to the choice of meta step-size `mu`.
+///
+/// # References
+/// - Mahmood, A. R., Sutton, R. S., Degris, T., & Pilarski, P. M. (2012).
+/// Tuning-free step-size adaptation. In Proceedings of the IEEE International
+/// Conference on Acoustics, Speech and Signal Processing, pp. 2121-2124.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Autostep {
+    pub mu: f64,
+    pub tau: f64,
+
+    alpha_init: f64,
+    alpha: Array1<f64>,
+    h: Array1<f64>,
+    v: Array1<f64>,
+}
+
+impl Autostep {
+    pub fn new(n_weights: usize, alpha: f64, mu: f64) -> Self {
+        Autostep {
+            mu,
+            tau: 1e4,
+
+            alpha_init: alpha,
+            alpha: Array1::from_elem(n_weights, alpha),
+            h: Array1::zeros(n_weights),
+            v: Array1::zeros(n_weights),
+        }
+    }
+
+    /// Return the current step-sizes.
+    pub fn step_sizes(&self) -> Array1<f64> { self.alpha.clone() }
+}
+
+impl StepSize for Autostep {
+    fn update(
+        &mut self,
+        mut weights: ArrayViewMut1<f64>,
+        phi: ArrayView1<f64>,
+        trace: ArrayView1<f64>,
+        error: f64,
+    )
+    {
+        let n = weights.len();
+
+        for i in 0..n {
+            let g = error * trace[i] * self.h[i];
+            let xe = phi[i] * trace[i];
+
+            self.v[i] = g
+                .abs()
+                .max(self.v[i] + self.alpha[i] * xe * (g.abs() - self.v[i]) / self.tau);
+
+            if self.v[i] > 0.0 {
+                self.alpha[i] *= (self.mu * g / self.v[i]).exp();
+            }
+        }
+
+        let norm = (0..n)
+            .fold(0.0, |acc, i| acc + self.alpha[i] * phi[i] * trace[i])
+            .max(1.0);
+
+        for i in 0..n {
+            let xe = phi[i] * trace[i];
+
+            self.alpha[i] /= norm;
+
+            weights[i] += self.alpha[i] * error * trace[i];
+            self.h[i] = self.h[i] * (1.0 - self.alpha[i] * xe) + self.alpha[i] * error * trace[i];
+        }
+    }
+
+    fn reset(&mut self) {
+        self.alpha.fill(self.alpha_init);
+        self.h.fill(0.0);
+        self.v.fill(0.0);
+    }
+}
+
+impl_optimiser!(Autostep);
+
+/// Scalar step-size which is monotonically reduced so that no single update
+/// overshoots its own target.
+///
+/// The bound used here, `alpha <= 1 / |e^T phi|`, is that of Dabney & Barto
+/// with the successor feature term dropped, since the successor features are
+/// not available to the function approximator.
+///
+/// # References
+/// - Dabney, W., & Barto, A. G. (2012). Adaptive step-size for online temporal
+/// difference learning. In Proceedings of the 26th AAAI Conference on
+/// Artificial Intelligence, pp. 872-878.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct AlphaBound {
+    alpha_init: f64,
+    alpha: f64,
+}
+
+impl AlphaBound {
+    pub fn new(alpha: f64) -> Self {
+        AlphaBound {
+            alpha_init: alpha,
+            alpha,
+        }
+    }
+
+    /// Return the current step-size.
+    pub fn step_size(&self) -> f64 { self.alpha }
+}
+
+impl StepSize for AlphaBound {
+    fn update(
+        &mut self,
+        mut weights: ArrayViewMut1<f64>,
+        phi: ArrayView1<f64>,
+        trace: ArrayView1<f64>,
+        error: f64,
+    )
+    {
+        let bound = trace.iter().zip(phi.iter()).fold(0.0, |acc, (e, x)| acc + e * x).abs();
+
+        if bound > 0.0 {
+            self.alpha = self.alpha.min(1.0 / bound);
+        }
+
+        weights.scaled_add(self.alpha * error, &trace);
+    }
+
+    fn reset(&mut self) { self.alpha = self.alpha_init; }
+}
+
+impl_optimiser!(AlphaBound);
+
+/// Wrapper applying an adaptive step-size rule to every update of a
+/// differentiable function approximator.
+///
+/// The wrapper records the most recent gradient computed through it so that
+/// trace-based updates (`ScaledGradientUpdate`) can be adapted with respect to
+/// the current features.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Adaptive<F, R> {
+    #[weights]
+    pub fa: F,
+    pub rule: R,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    features: RefCell<Option<Array1<f64>>>,
+}
+
+impl<F, R> Adaptive<F, R> {
+    pub fn new(fa: F, rule: R) -> Self {
+        Adaptive {
+            fa,
+            rule,
+
+            features: RefCell::new(None),
+        }
+    }
+}
+
+impl<F: Parameterised, R: StepSize> Adaptive<F, R> {
+    fn apply(&mut self, phi: Option<Array1<f64>>, trace: Array1<f64>, error: f64) {
+        let phi = phi.unwrap_or_else(|| trace.clone());
+        let mut weights: Array1<f64> = self.fa.weights_view().iter().cloned().collect();
+
+        self.rule
+            .update(weights.view_mut(), phi.view(), trace.view(), error);
+
+        self.fa
+            .weights_view_mut()
+            .iter_mut()
+            .zip(weights.iter())
+            .for_each(|(w, x)| *w = *x);
+    }
+}
+
+fn flatten<J: Buffer>(jacobian: &J) -> Array1<f64> {
+    jacobian.to_dense().iter().cloned().collect()
+}
+
+impl<Args, F: Function<Args>, R> Function<Args> for Adaptive<F, R> {
+    type Output = F::Output;
+
+    fn evaluate(&self, args: Args) -> F::Output { self.fa.evaluate(args) }
+}
+
+impl<Args, F: Enumerable<Args>, R> Enumerable<Args> for Adaptive<F, R>
+where
+    F::Output: Index<usize> + IntoIterator<Item = <F::Output as Index<usize>>::Output>,
+
+    <F::Output as Index<usize>>::Output: Sized,
+    <F::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    fn len(&self, args: Args) -> usize { self.fa.len(args) }
+
+    fn evaluate_index(&self, args: Args, index: usize) -> <F::Output as Index<usize>>::Output {
+        self.fa.evaluate_index(args, index)
+    }
+}
+
+impl<Args, F: Differentiable<Args>, R> Differentiable<Args> for Adaptive<F, R> {
+    type Jacobian = F::Jacobian;
+
+    fn grad(&self, args: Args) -> F::Jacobian {
+        let jac = self.fa.grad(args);
+
+        self.features.replace(Some(flatten(&jac)));
+
+        jac
+    }
+
+    fn grad_log(&self, args: Args) -> F::Jacobian { self.fa.grad_log(args) }
+}
+
+impl<S, F, R> Handler<StateUpdate<S, f64>> for Adaptive<F, R>
+where
+    F: Differentiable<(S,)>,
+    R: StepSize,
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: StateUpdate<S, f64>) -> Result<(), ()> {
+        let phi = flatten(&self.fa.grad((msg.state,)));
+
+        self.apply(None, phi, msg.error);
+
+        Ok(())
+    }
+}
+
+impl<S, A, F, R> Handler<StateActionUpdate<S, A, f64>> for Adaptive<F, R>
+where
+    F: Differentiable<(S, A)>,
+    R: StepSize,
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: StateActionUpdate<S, A, f64>) -> Result<(), ()> {
+        let phi = flatten(&self.fa.grad((msg.state, msg.action)));
+
+        self.apply(None, phi, msg.error);
+
+        Ok(())
+    }
+}
+
+impl<J, F, R> Handler<GradientUpdate<J>> for Adaptive<F, R>
+where
+    J: Buffer,
+    F: Parameterised,
+    R: StepSize,
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: GradientUpdate<J>) -> Result<(), ()> {
+        self.handle(ScaledGradientUpdate {
+            alpha: 1.0,
+            jacobian: msg.0,
+        })
+    }
+}
+
+impl<J, F, R> Handler<ScaledGradientUpdate<J>> for Adaptive<F, R>
+where
+    J: Buffer,
+    F: Parameterised,
+    R: StepSize,
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: ScaledGradientUpdate<J>) -> Result<(), ()> {
+        let phi = self.features.borrow_mut().take();
+
+        self.apply(phi, flatten(&msg.jacobian), msg.alpha);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fa::linear::{basis::Closure, optim::SGD, Features, LFA};
+    use ndarray::{arr1, Array1};
+
+    fn regress<R: StepSize>(mut rule: R) -> Array1<f64> {
+        let mut w = Array1::zeros(2);
+        let xs = [arr1(&[1.0, 0.0]), arr1(&[0.0, 2.0]), arr1(&[1.0, 1.0])];
+
+        for i in 0..2000 {
+            let x = &xs[i % 3];
+            let error = x[0] - 0.5 * x[1] - (w[0] * x[0] + w[1] * x[1]);
+
+            rule.update(w.view_mut(), x.view(), x.view(), error);
+        }
+
+        w
+    }
+
+    #[test]
+    fn test_idbd() {
+        let w = regress(IDBD::new(2, 0.05, 0.01));
+
+        assert!((w[0] - 1.0).abs() < 1e-3);
+        assert!((w[1] + 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_autostep() {
+        let w = regress(Autostep::new(2, 0.05, 0.01));
+
+        assert!((w[0] - 1.0).abs() < 1e-3);
+        assert!((w[1] + 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_alpha_bound() {
+        let mut rule = AlphaBound::new(10.0);
+        let w = regress(rule.clone());
+
+        assert!((w[0] - 1.0).abs() < 1e-3);
+        assert!((w[1] + 0.5).abs() < 1e-3);
+
+        rule.update(
+            Array1::zeros(2).view_mut(),
+            arr1(&[0.0, 2.0]).view(),
+            arr1(&[0.0, 2.0]).view(),
+            1.0,
+        );
+
+        assert!((rule.step_size() - 0.25).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_adaptive_lfa() {
+        let basis = Closure::new(1, |_: &()| Ok(Features::Dense(arr1(&[1.0]))));
+        let mut fa = Adaptive::new(LFA::scalar(basis, SGD(0.0)), IDBD::new(1, 0.1, 0.01));
+
+        for _ in 0..500 {
+            let error = 3.0 - fa.weights_view()[(0, 0)];
+
+            fa.handle(StateUpdate {
+                state: &(),
+                error,
+            })
+            .ok();
+        }
+
+        assert!((fa.weights_view()[(0, 0)] - 3.0).abs() < 1e-3);
+    }
+}
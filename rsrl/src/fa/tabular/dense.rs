@@ -1,24 +1,62 @@
 use super::Table;
 use crate::{
-    fa::{StateActionUpdate, StateUpdate},
+    fa::{GradientUpdate, ScaledGradientUpdate, StateActionUpdate, StateUpdate},
+    params::Buffer,
     Differentiable,
     Enumerable,
     Function,
     Handler,
 };
-use ndarray::{Array, Array1, Array2, Axis, Dimension, Ix1, Ix2};
+use ndarray::{Array, Array1, Array2, Axis, Dimension, Ix1, Ix2, ShapeBuilder};
 use std::{borrow::Borrow, ops::AddAssign};
 
 impl<D: Dimension> Table<Array<f64, D>> {
     pub fn dense(weights: Array<f64, D>) -> Self { Table(weights) }
 
     pub fn zeros(dim: D) -> Self { Table::dense(Array::zeros(dim)) }
+
+    /// Construct a table with every entry initialised optimistically to
+    /// `value`, e.g. an upper bound on the return, `r_max / (1 - γ)`.
+    pub fn optimistic<Sh>(shape: Sh, value: f64) -> Self
+    where Sh: ShapeBuilder<Dim = D> {
+        Table::dense(Array::from_elem(shape, value))
+    }
 }
 
 impl<D: Dimension> From<Array<f64, D>> for Table<Array<f64, D>> {
     fn from(w: Array<f64, D>) -> Table<Array<f64, D>> { Table::dense(w) }
 }
 
+impl<D, J> Handler<GradientUpdate<J>> for Table<Array<f64, D>>
+where
+    D: Dimension,
+    J: Buffer<Dim = D>,
+{
+    type Response = super::Response;
+    type Error = super::Error;
+
+    fn handle(&mut self, msg: GradientUpdate<J>) -> Result<Self::Response, Self::Error> {
+        msg.0.addto(&mut self.0);
+
+        Ok(super::Response)
+    }
+}
+
+impl<D, J> Handler<ScaledGradientUpdate<J>> for Table<Array<f64, D>>
+where
+    D: Dimension,
+    J: Buffer<Dim = D>,
+{
+    type Response = super::Response;
+    type Error = super::Error;
+
+    fn handle(&mut self, msg: ScaledGradientUpdate<J>) -> Result<Self::Response, Self::Error> {
+        msg.jacobian.scaled_addto(msg.alpha, &mut self.0);
+
+        Ok(super::Response)
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 // Implement V(s)
 ///////////////////////////////////////////////////////////////////////////////////////////////////
@@ -125,3 +163,18 @@ impl<S: Borrow<usize>, A: Borrow<usize>> Handler<StateActionUpdate<S, A>> for Ta
         Ok(super::Response)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimistic() {
+        let table = Table::optimistic((2, 3), 4.0);
+
+        for s in 0..2 {
+            assert_eq!(table.evaluate((s,)), vec![4.0; 3]);
+            assert_eq!(table.evaluate((s, 2)), 4.0);
+        }
+    }
+}
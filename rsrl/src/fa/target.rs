@@ -0,0 +1,132 @@
+use crate::{params::Parameterised, Enumerable, Function};
+use std::ops::Index;
+
+/// Rule used to synchronise a target function with its online counterpart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum TargetSync {
+    /// Copy the online weights every `n` updates.
+    Hard(usize),
+
+    /// Polyak averaging, `w' = tau * w + (1 - tau) * w'`, after every update.
+    Soft(f64),
+}
+
+/// Slowly-updated copy of a function approximator used for computing
+/// bootstrap targets.
+///
+/// The target holds its own set of weights, which must not be shared with the
+/// online function; when the latter is wrapped in `Shared`, construct the
+/// target from a deep copy, e.g. `make_shared(q_func.borrow().clone())`.
+///
+/// # References
+/// - Mnih, V., et al. (2015). Human-level control through deep reinforcement
+/// learning. Nature, 518(7540), 529-533.
+/// - Lillicrap, T. P., et al. (2016). Continuous control with deep
+/// reinforcement learning. In Proceedings of ICLR.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct TargetFunction<F> {
+    #[weights]
+    pub fa: F,
+    pub sync: TargetSync,
+
+    updates: usize,
+}
+
+impl<F> TargetFunction<F> {
+    pub fn new(fa: F, sync: TargetSync) -> Self {
+        TargetFunction {
+            fa,
+            sync,
+
+            updates: 0,
+        }
+    }
+
+    /// Construct a target that is overwritten every `period` updates.
+    pub fn hard(fa: F, period: usize) -> Self { TargetFunction::new(fa, TargetSync::Hard(period)) }
+
+    /// Construct a target that tracks the online weights at rate `tau`.
+    pub fn soft(fa: F, tau: f64) -> Self { TargetFunction::new(fa, TargetSync::Soft(tau)) }
+}
+
+impl<F: Parameterised> TargetFunction<F> {
+    /// Overwrite the target weights with those of `online`.
+    pub fn sync_from<P: Parameterised>(&mut self, online: &P) {
+        self.fa.weights_view_mut().assign(&online.weights_view());
+    }
+
+    /// Register a single update of `online`, synchronising if required.
+    pub fn update<P: Parameterised>(&mut self, online: &P) {
+        self.updates += 1;
+
+        match self.sync {
+            TargetSync::Hard(period) => if self.updates >= period {
+                self.updates = 0;
+                self.sync_from(online);
+            },
+            TargetSync::Soft(tau) => {
+                let online = online.weights_view();
+
+                self.fa.weights_view_mut().zip_mut_with(&online, |w, &v| {
+                    *w = tau * v + (1.0 - tau) * *w
+                });
+            },
+        }
+    }
+}
+
+impl<Args, F: Function<Args>> Function<Args> for TargetFunction<F> {
+    type Output = F::Output;
+
+    fn evaluate(&self, args: Args) -> F::Output { self.fa.evaluate(args) }
+}
+
+impl<Args, F: Enumerable<Args>> Enumerable<Args> for TargetFunction<F>
+where
+    F::Output: Index<usize> + IntoIterator<Item = <F::Output as Index<usize>>::Output>,
+
+    <F::Output as Index<usize>>::Output: Sized,
+    <F::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fa::tabular::Table;
+    use ndarray::Array2;
+
+    #[test]
+    fn test_hard() {
+        let online = Table::dense(Array2::from_elem((1, 1), 1.0));
+        let mut target = TargetFunction::hard(Table::dense(Array2::zeros((1, 1))), 2);
+
+        target.update(&online);
+        assert_eq!(target.evaluate((0usize, 0usize)), 0.0);
+
+        target.update(&online);
+        assert_eq!(target.evaluate((0usize, 0usize)), 1.0);
+    }
+
+    #[test]
+    fn test_soft() {
+        let online = Table::dense(Array2::from_elem((1, 1), 1.0));
+        let mut target = TargetFunction::soft(Table::dense(Array2::zeros((1, 1))), 0.5);
+
+        target.update(&online);
+        assert!((target.evaluate((0usize, 0usize)) - 0.5).abs() < 1e-7);
+
+        target.update(&online);
+        assert!((target.evaluate((0usize, 0usize)) - 0.75).abs() < 1e-7);
+    }
+}
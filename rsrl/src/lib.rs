@@ -35,6 +35,13 @@ pub mod params;
 #[macro_use]
 pub mod fa;
 pub mod traces;
+pub mod counts;
+pub mod schedules;
+pub mod optim;
 pub mod prediction;
 pub mod control;
 pub mod policies;
+pub mod ope;
+
+#[cfg(feature = "checkpoint")]
+pub mod checkpoint;
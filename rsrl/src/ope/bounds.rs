@@ -0,0 +1,94 @@
+use super::{estimators::weight, Estimator, Step};
+use rand::Rng;
+
+/// Method used to construct a confidence interval around an estimate.
+///
+/// Both methods return the uninformative interval `(-∞, ∞)` when there are no
+/// episodes to draw on.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum Interval {
+    /// Percentile bootstrap over episodes.
+    Bootstrap { n_resamples: usize },
+
+    /// Two-sided Hoeffding bound, assuming each per-episode term lies in an
+    /// interval of width `range`.
+    ///
+    /// For the weighted estimators the bound is approximate: the number of
+    /// episodes is replaced by the effective sample size of the importance
+    /// weights.
+    Hoeffding { range: f64 },
+}
+
+pub(super) fn bootstrap<R: Rng + ?Sized>(
+    rng: &mut R,
+    estimator: Estimator,
+    episodes: &[&[Step]],
+    gamma: f64,
+    n_resamples: usize,
+    delta: f64,
+) -> (f64, f64)
+{
+    let n = episodes.len();
+
+    if n == 0 || n_resamples == 0 {
+        return (f64::NEG_INFINITY, f64::INFINITY);
+    }
+
+    let mut resample = Vec::with_capacity(n);
+    let mut estimates: Vec<f64> = (0..n_resamples)
+        .map(|_| {
+            resample.clear();
+            resample.extend((0..n).map(|_| episodes[rng.gen_range(0, n)]));
+
+            estimator.estimate(&resample, gamma)
+        })
+        .collect();
+
+    estimates.sort_by(|a, b| a.total_cmp(b));
+
+    let quantile = |q: f64| {
+        let i = (q * (n_resamples - 1) as f64).round() as usize;
+
+        estimates[i.min(n_resamples - 1)]
+    };
+
+    (quantile(delta / 2.0), quantile(1.0 - delta / 2.0))
+}
+
+pub(super) fn hoeffding(
+    estimator: Estimator,
+    episodes: &[&[Step]],
+    value: f64,
+    range: f64,
+    delta: f64,
+) -> (f64, f64)
+{
+    let n = if estimator.is_weighted() {
+        let (sum, sum_sq) = episodes.iter().fold((0.0, 0.0), |(s, ss), e| {
+            let w = weight(e);
+
+            (s + w, ss + w * w)
+        });
+
+        if sum_sq > 0.0 {
+            sum * sum / sum_sq
+        } else {
+            0.0
+        }
+    } else {
+        episodes.len() as f64
+    };
+
+    if n <= 0.0 {
+        return (f64::NEG_INFINITY, f64::INFINITY);
+    }
+
+    let width = range * ((2.0 / delta).ln() / (2.0 * n)).sqrt();
+
+    (value - width, value + width)
+}
@@ -0,0 +1,134 @@
+use super::Step;
+
+/// Off-policy estimator of the value of a target policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum Estimator {
+    /// Ordinary (trajectory-wise) importance sampling.
+    IS,
+
+    /// Weighted importance sampling.
+    WIS,
+
+    /// Per-decision importance sampling.
+    PDIS,
+
+    /// Doubly robust estimator.
+    DR,
+
+    /// Weighted doubly robust estimator.
+    WDR,
+}
+
+impl Estimator {
+    /// Returns true if the estimator normalises by the importance weights.
+    pub fn is_weighted(&self) -> bool { matches!(self, Estimator::WIS | Estimator::WDR) }
+
+    pub(super) fn estimate(&self, episodes: &[&[Step]], gamma: f64) -> f64 {
+        if episodes.is_empty() {
+            return 0.0;
+        }
+
+        match self {
+            Estimator::IS => mean(episodes.iter().map(|e| is_term(e, gamma))),
+            Estimator::WIS => {
+                let (num, den) = episodes.iter().fold((0.0, 0.0), |(num, den), e| {
+                    let w = weight(e);
+
+                    (num + w * discounted_return(e, gamma), den + w)
+                });
+
+                if den > 0.0 {
+                    num / den
+                } else {
+                    0.0
+                }
+            },
+            Estimator::PDIS => mean(episodes.iter().map(|e| pdis_term(e, gamma))),
+            Estimator::DR => mean(episodes.iter().map(|e| dr_term(e, gamma))),
+            Estimator::WDR => wdr(episodes, gamma),
+        }
+    }
+}
+
+fn mean<I: Iterator<Item = f64>>(iter: I) -> f64 {
+    let (sum, n) = iter.fold((0.0, 0usize), |(sum, n), x| (sum + x, n + 1));
+
+    sum / n as f64
+}
+
+/// Product of the importance ratios over the whole episode.
+pub(super) fn weight(episode: &[Step]) -> f64 { episode.iter().map(|s| s.rho).product() }
+
+fn discounted_return(episode: &[Step], gamma: f64) -> f64 {
+    episode
+        .iter()
+        .rev()
+        .fold(0.0, |acc, s| s.reward + gamma * acc)
+}
+
+fn is_term(episode: &[Step], gamma: f64) -> f64 {
+    weight(episode) * discounted_return(episode, gamma)
+}
+
+fn pdis_term(episode: &[Step], gamma: f64) -> f64 {
+    episode
+        .iter()
+        .fold((0.0, 1.0, 1.0), |(acc, w, g), s| {
+            let w = w * s.rho;
+
+            (acc + g * w * s.reward, w, g * gamma)
+        })
+        .0
+}
+
+fn dr_term(episode: &[Step], gamma: f64) -> f64 {
+    episode
+        .iter()
+        .rev()
+        .fold(0.0, |acc, s| s.v + s.rho * (s.reward + gamma * acc - s.q))
+}
+
+fn wdr(episodes: &[&[Step]], gamma: f64) -> f64 {
+    let horizon = episodes.iter().map(|e| e.len()).max().unwrap_or(0);
+
+    // Cumulative importance weights, held constant after termination:
+    let mut ws_prev = vec![1.0; episodes.len()];
+    let mut ws = ws_prev.clone();
+
+    let mut total = 0.0;
+    let mut discount = 1.0;
+
+    for t in 0..horizon {
+        for (w, e) in ws.iter_mut().zip(episodes.iter()) {
+            if let Some(s) = e.get(t) {
+                *w *= s.rho;
+            }
+        }
+
+        let norm: f64 = ws.iter().sum();
+        let norm_prev: f64 = ws_prev.iter().sum();
+
+        for (i, e) in episodes.iter().enumerate() {
+            if let Some(s) = e.get(t) {
+                let w = if norm > 0.0 { ws[i] / norm } else { 0.0 };
+                let w_prev = if norm_prev > 0.0 {
+                    ws_prev[i] / norm_prev
+                } else {
+                    0.0
+                };
+
+                total += discount * (w * (s.reward - s.q) + w_prev * s.v);
+            }
+        }
+
+        ws_prev.copy_from_slice(&ws);
+        discount *= gamma;
+    }
+
+    total
+}
@@ -0,0 +1,327 @@
+//! Off-policy evaluation module.
+//!
+//! Estimators of the expected discounted return of a target policy computed
+//! from trajectories logged under a different behaviour policy.
+use crate::{domains::Trajectories, policies::Policy, Enumerable, Function};
+use rand::Rng;
+use std::ops::Index;
+
+mod bounds;
+mod estimators;
+
+pub use self::bounds::Interval;
+pub use self::estimators::Estimator;
+
+/// Value estimate with a two-sided confidence interval.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Estimate {
+    pub value: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Approximate model of the target policy's values used by the doubly robust
+/// estimators.
+pub trait Model<S, A, P> {
+    /// Return the estimates `(Q(s, a), V(s))` under the target `policy`.
+    fn values(&self, policy: &P, state: S, action: A) -> (f64, f64);
+}
+
+impl<S, A, P> Model<S, A, P> for () {
+    fn values(&self, _: &P, _: S, _: A) -> (f64, f64) { (0.0, 0.0) }
+}
+
+impl<'s, S, P, Q> Model<&'s S, &'s usize, P> for Q
+where
+    Q: Enumerable<(&'s S,)>,
+    P: Enumerable<(&'s S,)>,
+
+    <Q as Function<(&'s S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<Q as Function<(&'s S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+
+    <P as Function<(&'s S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<P as Function<(&'s S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    fn values(&self, policy: &P, state: &'s S, action: &'s usize) -> (f64, f64) {
+        let qs = self.evaluate((state,));
+        let v = policy
+            .evaluate((state,))
+            .into_iter()
+            .zip(qs)
+            .fold(0.0, |acc, (p, q)| acc + p * q);
+
+        (self.evaluate_index((state,), *action), v)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Step {
+    pub rho: f64,
+    pub reward: f64,
+    pub q: f64,
+    pub v: f64,
+}
+
+/// Off-policy evaluation of a `target` policy from data logged under a
+/// `behaviour` policy.
+///
+/// The doubly robust estimators additionally make use of an approximate
+/// `model` of the target policy's values, which may be any Q-function over
+/// discrete actions; without one they reduce to per-decision importance
+/// sampling.
+///
+/// # References
+/// - Precup, D., Sutton, R. S., & Singh, S. (2000). Eligibility traces for
+/// off-policy policy evaluation. In Proceedings of the 17th International
+/// Conference on Machine Learning, pp. 759-766.
+/// - Jiang, N., & Li, L. (2016). Doubly robust off-policy value evaluation for
+/// reinforcement learning. In Proceedings of the 33rd International
+/// Conference on Machine Learning, pp. 652-661.
+/// - Thomas, P. S., & Brunskill, E. (2016). Data-efficient off-policy policy
+/// evaluation for reinforcement learning. In Proceedings of the 33rd
+/// International Conference on Machine Learning, pp. 2139-2148.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Evaluator<P, B, M = ()> {
+    pub target: P,
+    pub behaviour: B,
+    pub model: M,
+
+    pub gamma: f64,
+}
+
+impl<P, B> Evaluator<P, B> {
+    pub fn new(target: P, behaviour: B, gamma: f64) -> Self {
+        Evaluator {
+            target,
+            behaviour,
+            model: (),
+
+            gamma,
+        }
+    }
+}
+
+impl<P, B, M> Evaluator<P, B, M> {
+    pub fn with_model<T>(self, model: T) -> Evaluator<P, B, T> {
+        Evaluator {
+            target: self.target,
+            behaviour: self.behaviour,
+            model,
+
+            gamma: self.gamma,
+        }
+    }
+
+    fn episodes<'m, S, A>(&self, trajectories: &'m Trajectories<S, A>) -> Vec<Vec<Step>>
+    where
+        P: Policy<&'m S, Action = A>,
+        B: Policy<&'m S, Action = A>,
+        M: Model<&'m S, &'m A, P>,
+    {
+        trajectories
+            .iter()
+            .map(|traj| {
+                traj.iter()
+                    .map(|t| {
+                        let s = *t.from.state();
+                        let (q, v) = self.model.values(&self.target, s, t.action);
+
+                        Step {
+                            rho: self.target.evaluate((s, t.action))
+                                / self.behaviour.evaluate((s, t.action)),
+                            reward: t.reward,
+                            q,
+                            v,
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Compute a point estimate of the target policy's value.
+    pub fn estimate<'m, S, A>(
+        &self,
+        estimator: Estimator,
+        trajectories: &'m Trajectories<S, A>,
+    ) -> f64
+    where
+        P: Policy<&'m S, Action = A>,
+        B: Policy<&'m S, Action = A>,
+        M: Model<&'m S, &'m A, P>,
+    {
+        let episodes = self.episodes(trajectories);
+        let episodes: Vec<&[Step]> = episodes.iter().map(|e| e.as_slice()).collect();
+
+        estimator.estimate(&episodes, self.gamma)
+    }
+
+    /// Compute an estimate of the target policy's value with a confidence
+    /// interval at level `confidence` (e.g. 0.95).
+    pub fn estimate_with_interval<'m, S, A, R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        estimator: Estimator,
+        interval: Interval,
+        confidence: f64,
+        trajectories: &'m Trajectories<S, A>,
+    ) -> Estimate
+    where
+        P: Policy<&'m S, Action = A>,
+        B: Policy<&'m S, Action = A>,
+        M: Model<&'m S, &'m A, P>,
+    {
+        let episodes = self.episodes(trajectories);
+        let episodes: Vec<&[Step]> = episodes.iter().map(|e| e.as_slice()).collect();
+
+        let delta = 1.0 - confidence;
+        let value = estimator.estimate(&episodes, self.gamma);
+
+        let (lower, upper) = match interval {
+            Interval::Bootstrap { n_resamples } => {
+                bounds::bootstrap(rng, estimator, &episodes, self.gamma, n_resamples, delta)
+            },
+            Interval::Hoeffding { range } => {
+                bounds::hoeffding(estimator, &episodes, value, range, delta)
+            },
+        };
+
+        Estimate {
+            value,
+            lower,
+            upper,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::{Observation, Trajectory},
+        fa::tabular::Table,
+        policies::{Greedy, Random, Softmax},
+    };
+    use ndarray::Array2;
+    use rand::thread_rng;
+
+    fn q_func() -> Table<Array2<f64>> {
+        let mut qs = Array2::zeros((1, 2));
+        qs[(0, 0)] = 1.0;
+
+        Table::dense(qs)
+    }
+
+    fn trajectories() -> Trajectories<usize, usize> {
+        vec![
+            Trajectory {
+                start: Observation::Full(0),
+                steps: vec![(Observation::Terminal(0), 0, 1.0)],
+            },
+            Trajectory {
+                start: Observation::Full(0),
+                steps: vec![(Observation::Terminal(0), 1, 0.0)],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_on_policy() {
+        let ope = Evaluator::new(Random::new(2), Random::new(2), 1.0);
+        let data = trajectories();
+
+        for &e in [Estimator::IS, Estimator::WIS, Estimator::PDIS, Estimator::DR, Estimator::WDR]
+            .iter()
+        {
+            assert!((ope.estimate(e, &data) - 0.5).abs() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_off_policy() {
+        let ope = Evaluator::new(Greedy::new(q_func()), Random::new(2), 1.0);
+        let data = trajectories();
+
+        assert!((ope.estimate(Estimator::IS, &data) - 1.0).abs() < 1e-7);
+        assert!((ope.estimate(Estimator::WIS, &data) - 1.0).abs() < 1e-7);
+
+        let ope = ope.with_model(q_func());
+
+        assert!((ope.estimate(Estimator::DR, &data) - 1.0).abs() < 1e-7);
+        assert!((ope.estimate(Estimator::WDR, &data) - 1.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_softmax_target() {
+        // π(· | 0) = (3/4, 1/4), so the true value is 3/4.
+        let mut prefs = Array2::zeros((1, 2));
+        prefs[(0, 0)] = 3.0f64.ln();
+
+        let target = Softmax::standard(Table::dense(prefs));
+        let ope = Evaluator::new(target, Random::new(2), 1.0);
+        let data = trajectories();
+
+        for &e in [Estimator::IS, Estimator::WIS, Estimator::PDIS, Estimator::DR, Estimator::WDR]
+            .iter()
+        {
+            assert!((ope.estimate(e, &data) - 0.75).abs() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_intervals() {
+        let ope = Evaluator::new(Random::new(2), Random::new(2), 1.0);
+        let data = trajectories();
+        let mut rng = thread_rng();
+
+        let est = ope.estimate_with_interval(
+            &mut rng,
+            Estimator::IS,
+            Interval::Bootstrap { n_resamples: 200 },
+            0.95,
+            &data,
+        );
+
+        assert!(est.lower <= est.value && est.value <= est.upper);
+        assert!(est.lower >= 0.0 && est.upper <= 1.0);
+
+        let est = ope.estimate_with_interval(
+            &mut rng,
+            Estimator::WIS,
+            Interval::Hoeffding { range: 1.0 },
+            0.95,
+            &data,
+        );
+
+        assert!(est.lower < 0.5 && est.upper > 0.5);
+    }
+
+    #[test]
+    fn test_empty_intervals() {
+        let ope = Evaluator::new(Random::new(2), Random::new(2), 1.0);
+        let data: Trajectories<usize, usize> = vec![];
+        let mut rng = thread_rng();
+
+        for &interval in [
+            Interval::Bootstrap { n_resamples: 200 },
+            Interval::Hoeffding { range: 1.0 },
+        ]
+        .iter()
+        {
+            let est = ope.estimate_with_interval(&mut rng, Estimator::IS, interval, 0.95, &data);
+
+            assert_eq!(est.lower, f64::NEG_INFINITY);
+            assert_eq!(est.upper, f64::INFINITY);
+        }
+    }
+}
@@ -0,0 +1,444 @@
+//! Stateful gradient-based optimisers.
+//!
+//! Optimisers are attached to any `Parameterised` type, typically a policy,
+//! using the `Optimised` wrapper. The wrapper intercepts the gradient
+//! messages that would otherwise be applied directly to the weights and
+//! routes them through the optimiser instead. All updates are gradient
+//! _ascent_ steps, consistent with `ScaledGradientUpdate`. These are distinct
+//! from the optimisers of `fa::linear::optim`, which belong to linear function
+//! approximators and apply their own update conventions.
+use crate::{
+    fa::{GradientUpdate, ScaledGradientUpdate, StateActionUpdate},
+    params::{Buffer, Parameterised, WeightsViewMut},
+    policies::Policy,
+    Differentiable,
+    Enumerable,
+    Function,
+    Handler,
+};
+use ndarray::{Array2, ArrayView2};
+use rand::Rng;
+use std::ops::Index;
+
+const EPS: f64 = 1e-8;
+
+/// Interface for stateful gradient-based optimisers.
+pub trait GradientOptimiser {
+    /// Take a step along the gradient `grad` scaled by `scale`.
+    fn step(&mut self, weights: WeightsViewMut, grad: ArrayView2<f64>, scale: f64);
+
+    /// Reset the internal state of the optimiser.
+    fn reset(&mut self) {}
+}
+
+fn fit(buffer: &mut Array2<f64>, dim: (usize, usize)) {
+    if buffer.dim() != dim {
+        *buffer = Array2::zeros(dim);
+    }
+}
+
+/// Vanilla stochastic gradient ascent with fixed learning rate.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct PlainSGD(pub f64);
+
+impl GradientOptimiser for PlainSGD {
+    fn step(&mut self, mut weights: WeightsViewMut, grad: ArrayView2<f64>, scale: f64) {
+        weights.scaled_add(self.0 * scale, &grad);
+    }
+}
+
+/// Stochastic gradient ascent with (optionally Nesterov) momentum.
+///
+/// # References
+/// - Sutskever, I., Martens, J., Dahl, G., & Hinton, G. (2013). On the
+/// importance of initialization and momentum in deep learning. In Proceedings
+/// of the 30th International Conference on Machine Learning, pp. 1139-1147.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Momentum {
+    pub learning_rate: f64,
+    pub momentum: f64,
+    pub nesterov: bool,
+
+    velocity: Array2<f64>,
+}
+
+impl Momentum {
+    pub fn new(learning_rate: f64, momentum: f64) -> Self {
+        Momentum {
+            learning_rate,
+            momentum,
+            nesterov: false,
+
+            velocity: Array2::zeros((0, 0)),
+        }
+    }
+
+    pub fn nesterov(learning_rate: f64, momentum: f64) -> Self {
+        Momentum {
+            nesterov: true,
+            ..Momentum::new(learning_rate, momentum)
+        }
+    }
+}
+
+impl GradientOptimiser for Momentum {
+    fn step(&mut self, mut weights: WeightsViewMut, grad: ArrayView2<f64>, scale: f64) {
+        fit(&mut self.velocity, weights.dim());
+
+        let mu = self.momentum;
+
+        self.velocity.zip_mut_with(&grad, |v, g| *v = mu * *v + scale * g);
+
+        if self.nesterov {
+            weights.scaled_add(self.learning_rate * mu, &self.velocity);
+            weights.scaled_add(self.learning_rate * scale, &grad);
+        } else {
+            weights.scaled_add(self.learning_rate, &self.velocity);
+        }
+    }
+
+    fn reset(&mut self) { self.velocity.fill(0.0); }
+}
+
+/// Gradient ascent normalised by a running average of squared gradients.
+///
+/// # References
+/// - Tieleman, T., & Hinton, G. (2012). Lecture 6.5 - RMSProp: Divide the
+/// gradient by a running average of its recent magnitude. COURSERA: Neural
+/// Networks for Machine Learning.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct RMSProp {
+    pub learning_rate: f64,
+    pub decay: f64,
+
+    mean_square: Array2<f64>,
+}
+
+impl RMSProp {
+    pub fn new(learning_rate: f64, decay: f64) -> Self {
+        RMSProp {
+            learning_rate,
+            decay,
+
+            mean_square: Array2::zeros((0, 0)),
+        }
+    }
+}
+
+impl GradientOptimiser for RMSProp {
+    fn step(&mut self, mut weights: WeightsViewMut, grad: ArrayView2<f64>, scale: f64) {
+        fit(&mut self.mean_square, weights.dim());
+
+        let (lr, rho) = (self.learning_rate, self.decay);
+
+        self.mean_square
+            .zip_mut_with(&grad, |ms, g| *ms = rho * *ms + (1.0 - rho) * (scale * g).powi(2));
+
+        ndarray::Zip::from(&mut weights)
+            .and(&grad)
+            .and(&self.mean_square)
+            .apply(|w, g, ms| *w += lr * scale * g / (ms.sqrt() + EPS));
+    }
+
+    fn reset(&mut self) { self.mean_square.fill(0.0); }
+}
+
+/// Adaptive moment estimation.
+///
+/// # References
+/// - Kingma, D. P., & Ba, J. (2015). Adam: A method for stochastic
+/// optimization. In Proceedings of the 3rd International Conference on
+/// Learning Representations.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Adam {
+    pub learning_rate: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+
+    m: Array2<f64>,
+    v: Array2<f64>,
+    t: i32,
+}
+
+impl Adam {
+    pub fn new(learning_rate: f64) -> Self {
+        Adam {
+            learning_rate,
+            beta1: 0.9,
+            beta2: 0.999,
+
+            m: Array2::zeros((0, 0)),
+            v: Array2::zeros((0, 0)),
+            t: 0,
+        }
+    }
+}
+
+impl GradientOptimiser for Adam {
+    fn step(&mut self, mut weights: WeightsViewMut, grad: ArrayView2<f64>, scale: f64) {
+        fit(&mut self.m, weights.dim());
+        fit(&mut self.v, weights.dim());
+
+        let (b1, b2) = (self.beta1, self.beta2);
+
+        self.t += 1;
+        self.m.zip_mut_with(&grad, |m, g| *m = b1 * *m + (1.0 - b1) * scale * g);
+        self.v
+            .zip_mut_with(&grad, |v, g| *v = b2 * *v + (1.0 - b2) * (scale * g).powi(2));
+
+        let lr = self.learning_rate * (1.0 - b2.powi(self.t)).sqrt() / (1.0 - b1.powi(self.t));
+
+        ndarray::Zip::from(&mut weights)
+            .and(&self.m)
+            .and(&self.v)
+            .apply(|w, m, v| *w += lr * m / (v.sqrt() + EPS));
+    }
+
+    fn reset(&mut self) {
+        self.m.fill(0.0);
+        self.v.fill(0.0);
+        self.t = 0;
+    }
+}
+
+/// Rescales the (scaled) gradient to have a norm of at most `max_norm` before
+/// passing it to the underlying optimiser.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct ClipNorm<O> {
+    pub optimiser: O,
+    pub max_norm: f64,
+}
+
+impl<O> ClipNorm<O> {
+    pub fn new(optimiser: O, max_norm: f64) -> Self {
+        ClipNorm {
+            optimiser,
+            max_norm,
+        }
+    }
+}
+
+impl<O: GradientOptimiser> GradientOptimiser for ClipNorm<O> {
+    fn step(&mut self, weights: WeightsViewMut, grad: ArrayView2<f64>, scale: f64) {
+        let norm = scale.abs() * grad.fold(0.0, |acc, g| acc + g * g).sqrt();
+        let scale = if norm > self.max_norm {
+            scale * self.max_norm / norm
+        } else {
+            scale
+        };
+
+        self.optimiser.step(weights, grad, scale)
+    }
+
+    fn reset(&mut self) { self.optimiser.reset() }
+}
+
+/// Wrapper that routes all gradient updates to a `Parameterised` type through
+/// a `GradientOptimiser`.
+///
+/// `StateActionUpdate` messages are treated as likelihood-ratio updates, i.e.
+/// a step along `error * grad_log(s, a)`, as sent by `ActorCritic` and
+/// `REINFORCE`. Since the optimiser carries its own learning rate, the
+/// learner's `alpha` would typically be set to 1.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Optimised<P, O> {
+    #[weights]
+    pub policy: P,
+    pub optimiser: O,
+}
+
+impl<P, O> Optimised<P, O> {
+    pub fn new(policy: P, optimiser: O) -> Self { Optimised { policy, optimiser } }
+}
+
+impl<P: Parameterised, O: GradientOptimiser> Optimised<P, O> {
+    fn apply<J: Buffer>(&mut self, jacobian: &J, scale: f64) {
+        let dim = self.policy.weights_dim();
+        let grad = Array2::from_shape_vec(dim, jacobian.to_dense().iter().cloned().collect())
+            .expect("Jacobian dimensions must match the weights.");
+
+        self.optimiser
+            .step(self.policy.weights_view_mut(), grad.view(), scale);
+    }
+}
+
+impl<Args, P: Function<Args>, O> Function<Args> for Optimised<P, O> {
+    type Output = P::Output;
+
+    fn evaluate(&self, args: Args) -> P::Output { self.policy.evaluate(args) }
+}
+
+impl<Args, P: Enumerable<Args>, O> Enumerable<Args> for Optimised<P, O>
+where
+    P::Output: Index<usize> + IntoIterator<Item = <P::Output as Index<usize>>::Output>,
+
+    <P::Output as Index<usize>>::Output: Sized,
+    <P::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    fn len(&self, args: Args) -> usize { self.policy.len(args) }
+
+    fn evaluate_index(&self, args: Args, index: usize) -> <P::Output as Index<usize>>::Output {
+        self.policy.evaluate_index(args, index)
+    }
+}
+
+impl<Args, P: Differentiable<Args>, O> Differentiable<Args> for Optimised<P, O> {
+    type Jacobian = P::Jacobian;
+
+    fn grad(&self, args: Args) -> P::Jacobian { self.policy.grad(args) }
+
+    fn grad_log(&self, args: Args) -> P::Jacobian { self.policy.grad_log(args) }
+}
+
+impl<S, P: Policy<S>, O> Policy<S> for Optimised<P, O> {
+    type Action = P::Action;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, state: S) -> P::Action {
+        self.policy.sample(rng, state)
+    }
+
+    fn mode(&self, state: S) -> P::Action { self.policy.mode(state) }
+}
+
+impl<S, A, P, O> Handler<StateActionUpdate<S, A, f64>> for Optimised<P, O>
+where
+    P: Differentiable<(S, A)>,
+    O: GradientOptimiser,
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: StateActionUpdate<S, A, f64>) -> Result<(), ()> {
+        let jac = self.policy.grad_log((msg.state, msg.action));
+
+        self.apply(&jac, msg.error);
+
+        Ok(())
+    }
+}
+
+impl<J, P, O> Handler<GradientUpdate<J>> for Optimised<P, O>
+where
+    J: Buffer,
+    P: Parameterised,
+    O: GradientOptimiser,
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: GradientUpdate<J>) -> Result<(), ()> {
+        self.apply(&msg.0, 1.0);
+
+        Ok(())
+    }
+}
+
+impl<J, P, O> Handler<ScaledGradientUpdate<J>> for Optimised<P, O>
+where
+    J: Buffer,
+    P: Parameterised,
+    O: GradientOptimiser,
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, msg: ScaledGradientUpdate<J>) -> Result<(), ()> {
+        self.apply(&msg.jacobian, msg.alpha);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fa::tabular::Table;
+    use ndarray::Array2;
+
+    // Maximise -(w - 3)^2 for each weight.
+    fn ascend<O: GradientOptimiser>(mut opt: O, n_steps: usize) -> f64 {
+        let mut w = Array2::zeros((1, 1));
+
+        for _ in 0..n_steps {
+            let grad = w.mapv(|x: f64| 2.0 * (3.0 - x));
+
+            opt.step(w.view_mut(), grad.view(), 1.0);
+        }
+
+        w[(0, 0)]
+    }
+
+    #[test]
+    fn test_sgd() {
+        assert!((ascend(PlainSGD(0.1), 200) - 3.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_momentum() {
+        assert!((ascend(Momentum::new(0.05, 0.9), 500) - 3.0).abs() < 1e-3);
+        assert!((ascend(Momentum::nesterov(0.05, 0.9), 500) - 3.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_rmsprop() {
+        assert!((ascend(RMSProp::new(0.01, 0.9), 2000) - 3.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_adam() {
+        assert!((ascend(Adam::new(0.1), 2000) - 3.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_clip_norm() {
+        let mut opt = ClipNorm::new(PlainSGD(1.0), 0.5);
+        let mut w = Array2::zeros((1, 2));
+
+        opt.step(w.view_mut(), Array2::from_elem((1, 2), 3.0).view(), 2.0);
+
+        assert!((w[(0, 0)].hypot(w[(0, 1)]) - 0.5).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_optimised() {
+        let mut fa = Optimised::new(Table::dense(Array2::zeros((2, 2))), Adam::new(0.1));
+
+        fa.handle(ScaledGradientUpdate {
+            alpha: 1000.0,
+            jacobian: Array2::from_elem((2, 2), 1.0),
+        })
+        .unwrap();
+
+        assert!(fa.weights_view().iter().all(|&w| (w - 0.1).abs() < 1e-6));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     policies::{Greedy, Policy, Random},
+    schedules::Schedule,
     Enumerable,
     Function,
 };
@@ -11,16 +12,16 @@ use rand::Rng;
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate")
 )]
-pub struct EpsilonGreedy<Q> {
+pub struct EpsilonGreedy<Q, E = f64> {
     #[weights]
     greedy: Greedy<Q>,
     random: Random,
 
-    pub epsilon: f64,
+    pub epsilon: E,
 }
 
-impl<Q> EpsilonGreedy<Q> {
-    pub fn new(greedy: Greedy<Q>, random: Random, epsilon: f64) -> Self {
+impl<Q, E: Schedule> EpsilonGreedy<Q, E> {
+    pub fn new(greedy: Greedy<Q>, random: Random, epsilon: E) -> Self {
         EpsilonGreedy {
             greedy,
             random,
@@ -30,22 +31,23 @@ impl<Q> EpsilonGreedy<Q> {
     }
 }
 
-impl<S, Q> Function<(S,)> for EpsilonGreedy<Q>
+impl<S, Q, E: Schedule> Function<(S,)> for EpsilonGreedy<Q, E>
 where Q: Enumerable<(S,), Output = Vec<f64>>
 {
     type Output = Vec<f64>;
 
     fn evaluate(&self, (s,): (S,)) -> Vec<f64> {
+        let epsilon = self.epsilon.value();
         let prs = self.greedy.evaluate((s,));
-        let pr = self.epsilon / prs.len() as f64;
+        let pr = epsilon / prs.len() as f64;
 
         prs.into_iter()
-            .map(|p| pr + p * (1.0 - self.epsilon))
+            .map(|p| pr + p * (1.0 - epsilon))
             .collect()
     }
 }
 
-impl<S, A, Q> Function<(S, A)> for EpsilonGreedy<Q>
+impl<S, A, Q, E: Schedule> Function<(S, A)> for EpsilonGreedy<Q, E>
 where
     A: std::borrow::Borrow<usize>,
     Q: Enumerable<(S,), Output = Vec<f64>>,
@@ -53,26 +55,27 @@ where
     type Output = f64;
 
     fn evaluate(&self, (s, a): (S, A)) -> f64 {
+        let epsilon = self.epsilon.value();
         let prs = self.greedy.evaluate((s,));
-        let pr = self.epsilon / prs.len() as f64;
+        let pr = epsilon / prs.len() as f64;
 
-        pr + (1.0 - self.epsilon) * prs[*a.borrow()]
+        pr + (1.0 - epsilon) * prs[*a.borrow()]
     }
 }
 
-impl<S, Q> Enumerable<(S,)> for EpsilonGreedy<Q>
+impl<S, Q, E: Schedule> Enumerable<(S,)> for EpsilonGreedy<Q, E>
 where Q: Enumerable<(S,), Output = Vec<f64>>
 {
     fn evaluate_index(&self, (s,): (S,), index: usize) -> f64 { self.evaluate((s, index)) }
 }
 
-impl<S, Q> Policy<S> for EpsilonGreedy<Q>
+impl<S, Q, E: Schedule> Policy<S> for EpsilonGreedy<Q, E>
 where Q: Enumerable<(S,), Output = Vec<f64>>
 {
     type Action = usize;
 
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: S) -> usize {
-        if rng.gen_bool(self.epsilon) {
+        if rng.gen_bool(self.epsilon.value()) {
             self.random.sample(rng, s)
         } else {
             self.greedy.sample(rng, s)
@@ -0,0 +1,187 @@
+use crate::{
+    policies::{sample_probs_with_rng, Policy},
+    schedules::Schedule,
+    utils::{argmax_first, mellowmax},
+    Enumerable,
+    Function,
+};
+use rand::Rng;
+use std::f64;
+
+/// Return the inverse temperature, `β`, of the maximum entropy distribution
+/// whose expected value equals the mellowmax of `values`.
+fn solve_beta(values: &[f64], tau: f64) -> f64 {
+    let mm = mellowmax(values, tau);
+    let diffs: Vec<f64> = values.iter().map(|v| v - mm).collect();
+    let max_diff = diffs.iter().fold(f64::MIN, |acc, &d| acc.max(d));
+
+    if max_diff < 1e-10 {
+        return 0.0;
+    }
+
+    // The root of Σ exp(β d_i) d_i is unchanged by scaling with exp(-β max_d).
+    let f = |beta: f64| {
+        diffs
+            .iter()
+            .fold(0.0, |acc, d| acc + (beta * (d - max_diff)).exp() * d)
+    };
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+
+    while f(hi) < 0.0 && hi < 1e10 {
+        lo = hi;
+        hi *= 2.0;
+    }
+
+    for _ in 0..64 {
+        let mid = (lo + hi) / 2.0;
+
+        if f(mid) < 0.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    (lo + hi) / 2.0
+}
+
+fn mellowmax_probs(values: &[f64], tau: f64) -> Vec<f64> {
+    let beta = solve_beta(values, tau);
+    let max_v = values.iter().fold(f64::MIN, |acc, &v| acc.max(v));
+
+    let ps: Vec<f64> = values.iter().map(|v| (beta * (v - max_v)).exp()).collect();
+    let z: f64 = ps.iter().sum();
+
+    ps.into_iter().map(|p| p / z).collect()
+}
+
+/// Maximum entropy mellowmax policy.
+///
+/// Actions are drawn from a Boltzmann distribution whose inverse temperature
+/// is chosen, per state, such that the expected action-value equals the
+/// mellowmax of the action-values with temperature `tau`. Unlike `Softmax`,
+/// the resulting operator is a non-expansion, which guarantees convergence of
+/// the associated backup.
+///
+/// # References
+/// - Asadi, K., Littman, M. L. (2017). An alternative softmax operator for
+/// reinforcement learning. In Proceedings of ICML, pp. 243-252.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Mellowmax<Q, T = f64> {
+    #[weights]
+    q_func: Q,
+
+    pub tau: T,
+}
+
+impl<Q, T: Schedule> Mellowmax<Q, T> {
+    pub fn new(q_func: Q, tau: T) -> Self {
+        if tau.value() < 1e-7 {
+            panic!("Tau parameter in Mellowmax must be positive.");
+        }
+
+        Mellowmax { q_func, tau }
+    }
+}
+
+impl<S, Q, T> Function<(S,)> for Mellowmax<Q, T>
+where
+    Q: Enumerable<(S,), Output = Vec<f64>>,
+    T: Schedule,
+{
+    type Output = Vec<f64>;
+
+    fn evaluate(&self, (s,): (S,)) -> Vec<f64> {
+        mellowmax_probs(&self.q_func.evaluate((s,)), self.tau.value())
+    }
+}
+
+impl<S, A, Q, T> Function<(S, A)> for Mellowmax<Q, T>
+where
+    A: std::borrow::Borrow<usize>,
+    Q: Enumerable<(S,), Output = Vec<f64>>,
+    T: Schedule,
+{
+    type Output = f64;
+
+    fn evaluate(&self, (s, a): (S, A)) -> f64 { self.evaluate((s,))[*a.borrow()] }
+}
+
+impl<S, Q, T> Enumerable<(S,)> for Mellowmax<Q, T>
+where
+    Q: Enumerable<(S,), Output = Vec<f64>>,
+    T: Schedule,
+{
+    fn len(&self, args: (S,)) -> usize { self.q_func.len(args) }
+
+    fn evaluate_index(&self, (s,): (S,), index: usize) -> f64 { self.evaluate((s, index)) }
+}
+
+impl<S, Q, T> Policy<S> for Mellowmax<Q, T>
+where
+    Q: Enumerable<(S,), Output = Vec<f64>>,
+    T: Schedule,
+{
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: S) -> usize {
+        sample_probs_with_rng(rng, &self.evaluate((s,)))
+    }
+
+    fn mode(&self, s: S) -> usize { argmax_first(self.q_func.evaluate((s,))).0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use crate::{
+        fa::mocking::MockQ,
+        policies::{Mellowmax, Policy},
+        utils::mellowmax,
+        Function,
+    };
+    use rand::thread_rng;
+
+    #[test]
+    #[should_panic]
+    fn test_zero_tau() { Mellowmax::new(MockQ::new_shared(None), 0.0); }
+
+    #[test]
+    fn test_probabilities_uniform() {
+        let p = Mellowmax::new(MockQ::new_shared(None), 1.0);
+
+        p.evaluate((vec![2.0, 2.0, 2.0, 2.0],))
+            .into_iter()
+            .for_each(|x| assert_abs_diff_eq!(x, 0.25, epsilon = 1e-6));
+    }
+
+    #[test]
+    fn test_expected_value() {
+        let p = Mellowmax::new(MockQ::new_shared(None), 0.5);
+        let qs = vec![1.0, 0.0, -1.0, 0.5];
+
+        let ps = p.evaluate((qs.clone(),));
+        let ev = ps.iter().zip(qs.iter()).fold(0.0, |acc, (p, q)| acc + p * q);
+
+        assert_abs_diff_eq!(ps.iter().sum::<f64>(), 1.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(ev, mellowmax(&qs, 0.5), epsilon = 1e-6);
+
+        assert!(ps[0] > ps[3]);
+        assert!(ps[3] > ps[1]);
+        assert!(ps[1] > ps[2]);
+    }
+
+    #[test]
+    fn test_mode() {
+        let p = Mellowmax::new(MockQ::new_shared(None), 1.0);
+
+        assert_eq!(p.mode(&vec![0.0, 1.0, 0.5]), 1);
+        assert!(p.sample(&mut thread_rng(), &vec![0.0, 1.0, 0.5]) < 3);
+    }
+}
@@ -17,24 +17,30 @@ use rand::{thread_rng, Rng};
 mod greedy;
 mod random;
 mod epsilon_greedy;
+mod ucb;
 
 pub use self::greedy::Greedy;
 pub use self::random::Random;
 pub use self::epsilon_greedy::EpsilonGreedy;
+pub use self::ucb::UCB;
 
 mod beta;
 mod gaussian;
 mod softmax;
+mod mellowmax;
 
 pub use self::beta::Beta;
 pub use self::gaussian::Gaussian;
 pub use self::softmax::{Gibbs, Softmax};
+pub use self::mellowmax::Mellowmax;
 
 mod ipp;
 mod point;
+mod noisy;
 
 pub use self::ipp::IPP;
 pub use self::point::Point;
+pub use self::noisy::Noisy;
 
 #[allow(dead_code)]
 #[inline]
@@ -43,7 +49,7 @@ pub(self) fn sample_probs(probabilities: &[f64]) -> usize {
 }
 
 #[inline]
-pub(self) fn sample_probs_with_rng<R: Rng + ?Sized>(rng: &mut R, probabilities: &[f64]) -> usize {
+pub(crate) fn sample_probs_with_rng<R: Rng + ?Sized>(rng: &mut R, probabilities: &[f64]) -> usize {
     let r = rng.gen::<f64>();
 
     match probabilities
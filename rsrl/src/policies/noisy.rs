@@ -0,0 +1,90 @@
+use crate::{policies::Policy, Function};
+use rand::Rng;
+use rstat::{
+    builder::{BuildNormal, Builder},
+    ContinuousDistribution,
+    Distribution,
+};
+
+/// Behaviour policy that perturbs the mode of a continuous policy with
+/// additive Gaussian noise.
+///
+/// This is typically used to explore around a deterministic target policy,
+/// such as a `Point`, when learning off-policy. The density of an action is
+/// that of `N(μ(s), stddev²)`, where `μ(s)` is the mode of the inner policy.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Noisy<P> {
+    #[weights]
+    pub policy: P,
+    pub stddev: f64,
+}
+
+impl<P> Noisy<P> {
+    pub fn new(policy: P, stddev: f64) -> Self { Noisy { policy, stddev } }
+
+    fn variance(&self) -> f64 { self.stddev * self.stddev }
+}
+
+impl<'x, X, A, P> Function<(&'x X, A)> for Noisy<P>
+where
+    A: std::borrow::Borrow<f64>,
+    P: Policy<&'x X, Action = f64>,
+{
+    type Output = f64;
+
+    fn evaluate(&self, (x, a): (&'x X, A)) -> f64 {
+        Builder::build_unchecked(self.policy.mode(x), self.variance()).pdf(a.borrow())
+    }
+}
+
+impl<'x, X, P> Policy<&'x X> for Noisy<P>
+where P: Policy<&'x X, Action = f64>
+{
+    type Action = f64;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, x: &'x X) -> f64 {
+        Builder::build_unchecked(self.policy.mode(x), self.variance()).sample(rng)
+    }
+
+    fn mode(&self, x: &'x X) -> f64 { self.policy.mode(x) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fa::tabular::Table, policies::Point};
+    use ndarray::Array1;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_density() {
+        let p = Noisy::new(Point::new(Table::dense(Array1::from(vec![1.5]))), 0.5);
+
+        assert_eq!(p.mode(&0), 1.5);
+
+        for &a in [1.5, 2.0, 0.0].iter() {
+            let z: f64 = (a - 1.5) / 0.5;
+            let pdf = (-0.5 * z * z).exp() / (0.5 * (2.0 * PI).sqrt());
+
+            assert!((p.evaluate((&0, a)) - pdf).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let p = Noisy::new(Point::new(Table::dense(Array1::from(vec![1.5]))), 0.5);
+        let samples: Vec<f64> = (0..5000).map(|_| p.sample(&mut rng, &0)).collect();
+        let mean = samples.iter().sum::<f64>() / 5000.0;
+        let var = samples.iter().map(|a| (a - mean).powi(2)).sum::<f64>() / 5000.0;
+
+        assert!((mean - 1.5).abs() < 0.05);
+        assert!((var - 0.25).abs() < 0.02);
+    }
+}
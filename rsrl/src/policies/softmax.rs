@@ -2,6 +2,7 @@ use crate::{
     fa::{GradientUpdate, ScaledGradientUpdate, StateActionUpdate},
     params::*,
     policies::{sample_probs_with_rng, Policy},
+    schedules::Schedule,
     utils::argmax_first,
     Differentiable,
     Enumerable,
@@ -36,7 +37,11 @@ fn softmax_stable<C: FromIterator<f64>>(values: &[f64], tau: f64) -> C {
     softmax(values, tau, max_v)
 }
 
-pub type Gibbs<F> = Softmax<F>;
+/// Smallest magnitude of temperature used to compute probabilities; schedules
+/// that decay towards zero are clamped here, recovering the greedy policy.
+const MIN_TAU: f64 = 1e-7;
+
+pub type Gibbs<F, T = f64> = Softmax<F, T>;
 
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(
@@ -52,54 +57,63 @@ pub struct Error;
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate")
 )]
-pub struct Softmax<F> {
+pub struct Softmax<F, T = f64> {
     #[weights]
     fa: F,
 
-    pub tau: f64,
+    pub tau: T,
 }
 
-impl<F> Softmax<F> {
-    pub fn new(fa: F, tau: f64) -> Self {
-        if tau.abs() < 1e-7 {
+impl<F, T: Schedule> Softmax<F, T> {
+    pub fn new(fa: F, tau: T) -> Self {
+        if tau.value().abs() < MIN_TAU {
             panic!("Tau parameter in Softmax must be non-zero.");
         }
 
         Softmax { fa, tau }
     }
+}
 
+impl<F> Softmax<F> {
     pub fn standard(fa: F) -> Self { Self::new(fa, 1.0) }
 }
 
-impl<'s, S, F: Function<(&'s S,), Output = Vec<f64>>> Function<(&'s S,)> for Softmax<F> {
+impl<'s, S, F, T> Function<(&'s S,)> for Softmax<F, T>
+where
+    F: Function<(&'s S,), Output = Vec<f64>>,
+    T: Schedule,
+{
     type Output = Vec<f64>;
 
     fn evaluate(&self, (s,): (&'s S,)) -> Vec<f64> {
         let values = self.fa.evaluate((s,));
+        let tau = self.tau.value();
 
-        softmax_stable(&values, self.tau)
+        if tau.abs() < MIN_TAU {
+            softmax_stable(&values, MIN_TAU.copysign(tau))
+        } else {
+            softmax_stable(&values, tau)
+        }
     }
 }
 
-impl<'s, S, A, F> Function<(&'s S, A)> for Softmax<F>
+impl<'s, S, A, F, T: Schedule> Function<(&'s S, A)> for Softmax<F, T>
 where
     A: std::borrow::Borrow<usize>,
-    F: Function<(&'s S, usize), Output = f64>,
+    F: Function<(&'s S,), Output = Vec<f64>>,
 {
     type Output = f64;
 
-    fn evaluate(&self, (s, a): (&'s S, A)) -> f64 { self.fa.evaluate((s, *a.borrow())) }
+    fn evaluate(&self, (s, a): (&'s S, A)) -> f64 { self.evaluate((s,))[*a.borrow()] }
 }
 
-impl<'s, S, F> Enumerable<(&'s S,)> for Softmax<F>
+impl<'s, S, F, T: Schedule> Enumerable<(&'s S,)> for Softmax<F, T>
 where F: Enumerable<(&'s S,), Output = Vec<f64>>
 {
-    fn evaluate_index(&self, (s,): (&'s S,), index: usize) -> f64 {
-        self.fa.evaluate_index((s,), index)
-    }
+    fn evaluate_index(&self, (s,): (&'s S,), index: usize) -> f64 { self.evaluate((s, index)) }
 }
 
-impl<'s, S, A, F> Differentiable<(&'s S, A)> for Softmax<F>
+impl<'s, S, A, F, T: Schedule> Differentiable<(&'s S, A)> for Softmax<F, T>
 where
     A: std::borrow::Borrow<usize>,
     F: Function<(&'s S, usize), Output = f64> + Parameterised,
@@ -128,7 +142,7 @@ where
     }
 }
 
-impl<'s, S, F> Policy<&'s S> for Softmax<F>
+impl<'s, S, F, T: Schedule> Policy<&'s S> for Softmax<F, T>
 where
     F: Function<(&'s S, usize), Output = f64> + Parameterised,
     F: Enumerable<(&'s S,), Output = Vec<f64>>,
@@ -142,7 +156,7 @@ where
     fn mode(&self, s: &'s S) -> usize { argmax_first(self.evaluate((s,))).0 }
 }
 
-impl<'s, S, A, F> Handler<StateActionUpdate<&'s S, A>> for Softmax<F>
+impl<'s, S, A, F, T: Schedule> Handler<StateActionUpdate<&'s S, A>> for Softmax<F, T>
 where
     A: std::borrow::Borrow<usize>,
     F: Handler<ScaledGradientUpdate<<Self as Differentiable<(&'s S, A)>>::Jacobian>>,
@@ -161,7 +175,7 @@ where
     }
 }
 
-impl<D, F> Handler<GradientUpdate<ArrayBase<D, Ix2>>> for Softmax<F>
+impl<D, F, T> Handler<GradientUpdate<ArrayBase<D, Ix2>>> for Softmax<F, T>
 where
     F: Parameterised,
     D: Data<Elem = f64>,
@@ -174,7 +188,7 @@ where
     }
 }
 
-impl<'m, D, F> Handler<GradientUpdate<&'m ArrayBase<D, Ix2>>> for Softmax<F>
+impl<'m, D, F, T> Handler<GradientUpdate<&'m ArrayBase<D, Ix2>>> for Softmax<F, T>
 where
     F: Parameterised,
     D: Data<Elem = f64>,
@@ -189,7 +203,7 @@ where
     }
 }
 
-impl<F, D> Handler<ScaledGradientUpdate<ArrayBase<D, Ix2>>> for Softmax<F>
+impl<F, D, T> Handler<ScaledGradientUpdate<ArrayBase<D, Ix2>>> for Softmax<F, T>
 where
     F: Parameterised,
     D: Data<Elem = f64>,
@@ -205,7 +219,7 @@ where
     }
 }
 
-impl<'m, F, D> Handler<ScaledGradientUpdate<&'m ArrayBase<D, Ix2>>> for Softmax<F>
+impl<'m, F, D, T> Handler<ScaledGradientUpdate<&'m ArrayBase<D, Ix2>>> for Softmax<F, T>
 where
     F: Parameterised,
     D: Data<Elem = f64>,
@@ -233,6 +247,7 @@ mod tests {
             },
             mocking::MockQ,
         },
+        schedules::Exponential,
     };
     use rand::thread_rng;
     use std::f64::consts::E;
@@ -245,6 +260,35 @@ mod tests {
         p.sample(&mut thread_rng(), &vec![]);
     }
 
+    #[test]
+    fn test_action_probabilities() {
+        let p = Softmax::new(MockQ::new_shared(None), 1.0);
+        let qs = vec![0.0, 1.0, -2.0];
+
+        let ps = p.evaluate((&qs,));
+        let total: f64 = (0..3).map(|a| p.evaluate((&qs, a))).sum();
+
+        assert!((total - 1.0).abs() < 1e-10);
+        assert!((p.evaluate((&qs, 1)) - E / (1.0 + E + (-2.0f64).exp())).abs() < 1e-10);
+        assert!((p.evaluate_index((&qs,), 2) - ps[2]).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_decayed_temperature() {
+        let mut p = Softmax::new(MockQ::new_shared(None), Exponential::new(1.0, 0.1));
+        let qs = vec![0.0, 1.0, -2.0];
+
+        while p.tau.value() > 0.0 {
+            p.tau.step();
+        }
+
+        let ps = p.evaluate((&qs,));
+
+        assert!(ps.iter().all(|p| p.is_finite()));
+        assert!((ps[1] - 1.0).abs() < 1e-10);
+        assert_eq!(p.sample(&mut thread_rng(), &qs), 1);
+    }
+
     #[test]
     fn test_1d() {
         let p = Softmax::new(MockQ::new_shared(None), 1.0);
@@ -0,0 +1,181 @@
+use crate::{
+    counts::Counts,
+    domains::Transition,
+    policies::Policy,
+    utils::{argmax_choose_rng, argmax_first, argmaxima},
+    Enumerable,
+    Function,
+    Handler,
+};
+use rand::Rng;
+use std::f64;
+
+/// Upper confidence bound policy over an enumerable action-value function.
+///
+/// Each action is scored by `Q(s, a) + c sqrt(ln N(s) / N(s, a))`, where the
+/// visitation counts, `N`, are held by `counts` and updated by passing each
+/// `Transition` to `Handler::handle`. Actions that have never been taken in a
+/// state are always preferred, and ties are broken uniformly at random.
+///
+/// # References
+/// - Auer, P., Cesa-Bianchi, N., Fischer, P. (2002). Finite-time analysis of
+/// the multiarmed bandit problem. Machine Learning, 47(2-3), 235-256.
+/// - Kocsis, L., Szepesvári, C. (2006). Bandit based Monte-Carlo planning. In
+/// Proceedings of ECML, pp. 282-293.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct UCB<Q, C> {
+    #[weights]
+    q_func: Q,
+
+    pub counts: C,
+    pub c: f64,
+}
+
+impl<Q, C> UCB<Q, C> {
+    pub fn new(q_func: Q, counts: C, c: f64) -> Self { UCB { q_func, counts, c } }
+
+    /// Return the upper confidence bound of each action in state `s`.
+    pub fn indices<S: Clone>(&self, s: S) -> Vec<f64>
+    where
+        Q: Enumerable<(S,), Output = Vec<f64>>,
+        C: Counts<S>,
+    {
+        let ln_n = (self.counts.total(s.clone()).max(1) as f64).ln();
+
+        self.q_func
+            .evaluate((s.clone(),))
+            .into_iter()
+            .enumerate()
+            .map(|(a, q)| match self.counts.count(s.clone(), a) {
+                0 => f64::MAX,
+                n => q + self.c * (ln_n / n as f64).sqrt(),
+            })
+            .collect()
+    }
+}
+
+impl<S, Q, C> Function<(S,)> for UCB<Q, C>
+where
+    S: Clone,
+    Q: Enumerable<(S,), Output = Vec<f64>>,
+    C: Counts<S>,
+{
+    type Output = Vec<f64>;
+
+    fn evaluate(&self, (s,): (S,)) -> Vec<f64> {
+        let indices = self.indices(s);
+        let (maxima, _) = argmaxima(indices.iter().cloned());
+
+        let mut ps = vec![0.0; indices.len()];
+        let p = 1.0 / maxima.len() as f64;
+
+        for i in maxima {
+            ps[i] = p;
+        }
+
+        ps
+    }
+}
+
+impl<S, A, Q, C> Function<(S, A)> for UCB<Q, C>
+where
+    S: Clone,
+    A: std::borrow::Borrow<usize>,
+    Q: Enumerable<(S,), Output = Vec<f64>>,
+    C: Counts<S>,
+{
+    type Output = f64;
+
+    fn evaluate(&self, (s, a): (S, A)) -> f64 { self.evaluate((s,))[*a.borrow()] }
+}
+
+impl<S, Q, C> Enumerable<(S,)> for UCB<Q, C>
+where
+    S: Clone,
+    Q: Enumerable<(S,), Output = Vec<f64>>,
+    C: Counts<S>,
+{
+    fn len(&self, args: (S,)) -> usize { self.q_func.len(args) }
+
+    fn evaluate_index(&self, (s,): (S,), index: usize) -> f64 { self.evaluate((s, index)) }
+}
+
+impl<S, Q, C> Policy<S> for UCB<Q, C>
+where
+    S: Clone,
+    Q: Enumerable<(S,), Output = Vec<f64>>,
+    C: Counts<S>,
+{
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: S) -> usize {
+        argmax_choose_rng(rng, self.indices(s)).0
+    }
+
+    fn mode(&self, s: S) -> usize { argmax_first(self.q_func.evaluate((s,))).0 }
+}
+
+impl<'m, S, Q, C> Handler<&'m Transition<S, usize>> for UCB<Q, C>
+where C: Counts<&'m S>
+{
+    type Response = ();
+    type Error = ();
+
+    fn handle(&mut self, t: &'m Transition<S, usize>) -> Result<(), ()> {
+        self.counts.increment(t.from.state(), t.action);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        counts::{Counts, TabularCounts},
+        fa::tabular::Table,
+        policies::{Policy, UCB},
+        Function,
+    };
+    use ndarray::Array2;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_unvisited_first() {
+        let q = Table::dense(Array2::from_shape_vec((1, 3), vec![1.0, 0.0, 0.0]).unwrap());
+        let mut p = UCB::new(q, TabularCounts::new(1, 3), 1.0);
+
+        p.counts.increment(0, 0);
+        p.counts.increment(0, 1);
+
+        assert_eq!(p.sample(&mut thread_rng(), 0), 2);
+        assert_eq!(p.evaluate((0, 2)), 1.0);
+        assert_eq!(p.mode(0), 0);
+    }
+
+    #[test]
+    fn test_bonus() {
+        let q = Table::dense(Array2::from_shape_vec((1, 2), vec![1.0, 0.5]).unwrap());
+        let mut p = UCB::new(q, TabularCounts::new(1, 2), 1.0);
+
+        for _ in 0..9 {
+            p.counts.increment(0, 0);
+        }
+
+        p.counts.increment(0, 1);
+
+        // 1 + sqrt(ln 10 / 9) < 0.5 + sqrt(ln 10).
+        assert_eq!(p.evaluate((0,)), vec![0.0, 1.0]);
+
+        // Equal counts yield equal bonuses.
+        for _ in 0..8 {
+            p.counts.increment(0, 1);
+        }
+
+        assert_eq!(p.evaluate((0,)), vec![1.0, 0.0]);
+    }
+}
@@ -9,6 +9,11 @@ use ndarray::{Array1, Array2, Axis};
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct iLSTD<B> {
     pub basis: B,
     #[weights]
@@ -10,6 +10,11 @@ use ndarray_linalg::Solve;
 use std::ops::MulAssign;
 
 #[derive(Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct LambdaLSPE<B> {
     pub basis: B,
     #[weights]
@@ -0,0 +1,252 @@
+use super::PathPoint;
+use crate::{
+    domains::Batch,
+    fa::linear::{basis::Basis, Features},
+    utils::{argmax_first, pinv},
+    Handler,
+    Parameterised,
+};
+use ndarray::{Array1, Array2, Axis};
+use ndarray_linalg::Solve;
+use std::f64;
+
+const STEP_TOL: f64 = 1e-12;
+
+/// Least-squares temporal-difference learning with an L1 penalty, solved
+/// using least-angle regression (LARS-TD).
+///
+/// Each call to `solve` traces the piecewise-linear path of L1-regularised
+/// fixed points from the empty solution down to the target penalty `beta`,
+/// adding and removing features from the active set as it goes. The final
+/// solution is typically sparse.
+///
+/// # References
+/// - Kolter, J. Z., & Ng, A. Y. (2009). Regularization and feature selection
+/// in least-squares temporal difference learning. In Proceedings of the 26th
+/// International Conference on Machine Learning, pp. 521-528.
+#[derive(Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct LARSTD<B> {
+    pub basis: B,
+    #[weights]
+    pub theta: Array1<f64>,
+
+    pub gamma: f64,
+    pub beta: f64,
+    pub max_iter: usize,
+
+    a: Array2<f64>,
+    b: Array1<f64>,
+}
+
+impl<B: spaces::Space> LARSTD<B> {
+    pub fn new(basis: B, gamma: f64, beta: f64) -> Self {
+        let n_features: usize = basis.dim().into();
+
+        LARSTD {
+            basis,
+            theta: Array1::zeros(n_features),
+
+            gamma,
+            beta,
+            max_iter: 2 * n_features,
+
+            a: Array2::zeros((n_features, n_features)),
+            b: Array1::zeros(n_features),
+        }
+    }
+}
+
+impl<B> LARSTD<B> {
+    /// Trace the regularisation path, update the weights to the solution at
+    /// `beta` and return the knots of the path.
+    pub fn solve(&mut self) -> Vec<PathPoint> {
+        let n_features = self.b.len();
+
+        let mut theta = Array1::zeros(n_features);
+        let mut corr = self.b.clone();
+
+        let (i0, mut beta_bar) = argmax_first(corr.iter().map(|c| c.abs()));
+        let mut active = vec![i0];
+
+        let mut path = vec![PathPoint {
+            beta: beta_bar,
+            active: vec![],
+            theta: theta.clone(),
+        }];
+
+        for _ in 0..self.max_iter {
+            if beta_bar <= self.beta {
+                break;
+            } else if active.is_empty() {
+                active.push(argmax_first(corr.iter().map(|c| c.abs())).0);
+            }
+
+            // Find the update direction over the active set:
+            let a_ii = self.a.select(Axis(0), &active).select(Axis(1), &active);
+            let signs: Array1<f64> = active.iter().map(|&i| corr[i].signum()).collect();
+
+            let delta = match a_ii
+                .solve(&signs)
+                .or_else(|_| pinv(&a_ii).map(|ainv| ainv.dot(&signs)))
+            {
+                Ok(delta) => delta,
+                Err(_) => break,
+            };
+            let d = self.a.select(Axis(1), &active).dot(&delta);
+
+            // Find the step size at which an inactive feature joins:
+            let (mut alpha_add, mut i_add) = (f64::INFINITY, None);
+
+            for i in (0..n_features).filter(|i| !active.contains(i)) {
+                let step_lo = (corr[i] - beta_bar) / (d[i] - 1.0);
+                let step_hi = (corr[i] + beta_bar) / (d[i] + 1.0);
+
+                for step in [step_lo, step_hi].iter() {
+                    if *step > STEP_TOL && *step < alpha_add {
+                        alpha_add = *step;
+                        i_add = Some(i);
+                    }
+                }
+            }
+
+            // Find the step size at which an active feature crosses zero:
+            let (mut alpha_rm, mut k_rm) = (f64::INFINITY, None);
+
+            for (k, &i) in active.iter().enumerate() {
+                let step = -theta[i] / delta[k];
+
+                if step > STEP_TOL && step < alpha_rm {
+                    alpha_rm = step;
+                    k_rm = Some(k);
+                }
+            }
+
+            let alpha_end = beta_bar - self.beta;
+            let alpha = alpha_add.min(alpha_rm).min(alpha_end);
+
+            for (k, &i) in active.iter().enumerate() {
+                theta[i] += alpha * delta[k];
+            }
+
+            beta_bar -= alpha;
+            corr.scaled_add(-alpha, &d);
+
+            if alpha >= alpha_end {
+                path.push(PathPoint {
+                    beta: self.beta,
+                    active: active.clone(),
+                    theta: theta.clone(),
+                });
+
+                break;
+            } else if alpha_add < alpha_rm {
+                active.push(i_add.unwrap());
+            } else {
+                let i = active.remove(k_rm.unwrap());
+
+                theta[i] = 0.0;
+            }
+
+            path.push(PathPoint {
+                beta: beta_bar,
+                active: active.clone(),
+                theta: theta.clone(),
+            });
+        }
+
+        self.theta.assign(&theta);
+
+        path
+    }
+}
+
+impl<'m, S, A, B> Handler<&'m Batch<S, A>> for LARSTD<B>
+where B: Basis<&'m S, Value = Features>
+{
+    type Response = Vec<PathPoint>;
+    type Error = crate::fa::linear::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, A>) -> Result<Vec<PathPoint>, Self::Error> {
+        for t in batch {
+            let (s, ns) = t.states();
+
+            let phi_s = self.basis.project(s)?.into_dense();
+
+            self.b.scaled_add(t.reward, &phi_s);
+
+            if t.terminated() {
+                let phi_s = phi_s.insert_axis(Axis(1));
+
+                self.a += &phi_s.view().dot(&phi_s.t());
+            } else {
+                let phi_ns = self.basis.project(ns)?.into_dense();
+                let pd = (self.gamma * phi_ns - &phi_s).insert_axis(Axis(0));
+
+                self.a -= &phi_s.insert_axis(Axis(1)).dot(&pd);
+            }
+        }
+
+        Ok(self.solve())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::{Observation, Transition},
+        fa::linear::basis::Closure,
+        prediction::lstd::LSTD,
+    };
+
+    fn one_hot(s: &usize) -> Result<Features, crate::fa::linear::Error> {
+        let mut phi = Array1::zeros(3);
+        phi[*s] = 1.0;
+
+        Ok(Features::Dense(phi))
+    }
+
+    #[test]
+    fn test_chain() {
+        // Deterministic chain 0 -> 1 -> 2 -> terminal, rewarded on the last step.
+        let batch: Vec<_> = (0..3)
+            .map(|s| Transition {
+                from: Observation::Full(s),
+                action: (),
+                reward: if s == 2 { 1.0 } else { 0.0 },
+                to: if s == 2 {
+                    Observation::Terminal(0)
+                } else {
+                    Observation::Full(s + 1)
+                },
+            })
+            .collect();
+
+        let mut lstd = LSTD::new(Closure::new(3, one_hot), 0.9);
+        let mut lars = LARSTD::new(Closure::new(3, one_hot), 0.9, 1e-8);
+
+        lstd.handle(&batch).unwrap();
+
+        let path = lars.handle(&batch).unwrap();
+        let last = path.last().unwrap();
+
+        assert_eq!(path.len(), 4);
+        assert!((last.beta - 1e-8).abs() < 1e-12);
+        assert_eq!(last.active.len(), 3);
+
+        for i in 0..3 {
+            assert!((last.theta[i] - lstd.theta[i]).abs() < 1e-4);
+            assert!((lars.theta[i] - lstd.theta[i]).abs() < 1e-4);
+        }
+
+        for (prev, next) in path.iter().zip(path.iter().skip(1)) {
+            assert!(next.beta <= prev.beta);
+            assert!(prev.active.iter().all(|i| next.active.contains(i)));
+        }
+    }
+}
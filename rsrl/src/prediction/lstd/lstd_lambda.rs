@@ -9,6 +9,11 @@ use ndarray::{Array1, Array2, Axis};
 use ndarray_linalg::Solve;
 
 #[derive(Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct LSTDLambda<B> {
     pub basis: B,
     #[weights]
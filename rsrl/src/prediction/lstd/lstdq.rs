@@ -0,0 +1,194 @@
+use crate::{
+    domains::Batch,
+    fa::linear::{basis::Basis, Features, VectorLFA},
+    policies::EnumerablePolicy,
+    utils::pinv,
+    Function,
+    Handler,
+    Parameterised,
+};
+use ndarray::{Array1, Array2, Axis};
+use ndarray_linalg::Solve;
+use std::ops::Index;
+
+/// Accumulate the LSTDQ statistics for a single transition.
+///
+/// The state-action features are formed by placing `phi_s` in the block of
+/// the flattened weight vector associated with `action`; `next` holds the
+/// features of the successor state and the probability of each action under
+/// the evaluation policy.
+pub(crate) fn accumulate(
+    a: &mut Array2<f64>,
+    b: &mut Array1<f64>,
+    gamma: f64,
+    phi_s: Array1<f64>,
+    action: usize,
+    reward: f64,
+    next: Option<(Array1<f64>, &[f64])>,
+) {
+    let k = phi_s.len();
+    let block = action * k..(action + 1) * k;
+
+    b.slice_mut(s![block.clone()]).scaled_add(reward, &phi_s);
+
+    let phi_s = phi_s.insert_axis(Axis(1));
+
+    a.slice_mut(s![block.clone(), block.clone()])
+        .scaled_add(1.0, &phi_s.dot(&phi_s.t()));
+
+    if let Some((phi_ns, probs)) = next {
+        let outer = phi_s.dot(&phi_ns.insert_axis(Axis(0)));
+
+        for (na, &p) in probs.iter().enumerate().filter(|(_, p)| **p > 0.0) {
+            a.slice_mut(s![block.clone(), na * k..(na + 1) * k])
+                .scaled_add(-gamma * p, &outer);
+        }
+    }
+}
+
+/// Solve the LSTDQ system and return the weights in (n_features x n_actions)
+/// form.
+pub(crate) fn solve(a: &Array2<f64>, b: &Array1<f64>, n_actions: usize) -> Option<Array2<f64>> {
+    let n_features = b.len() / n_actions;
+
+    a.solve(b)
+        .or_else(|_| pinv(a).map(|ainv| ainv.dot(b)))
+        .ok()
+        .map(|flat| {
+            Array2::from_shape_vec((n_actions, n_features), flat.to_vec())
+                .unwrap()
+                .reversed_axes()
+        })
+}
+
+/// Least-squares temporal-difference learning of action values.
+///
+/// Evaluates the Q-function of an arbitrary enumerable `policy` using
+/// features formed by replicating the state basis for each action. The
+/// expectation over successor actions is taken exactly using the policy's
+/// probabilities.
+///
+/// # References
+/// - Lagoudakis, M. G., & Parr, R. (2003). Least-squares policy iteration.
+/// Journal of Machine Learning Research, 4, 1107-1149.
+#[derive(Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct LSTDQ<B, P> {
+    pub basis: B,
+    #[weights]
+    pub theta: Array2<f64>,
+
+    pub policy: P,
+
+    pub gamma: f64,
+
+    a: Array2<f64>,
+    b: Array1<f64>,
+}
+
+impl<B: spaces::Space, P> LSTDQ<B, P> {
+    pub fn new(basis: B, policy: P, n_actions: usize, gamma: f64) -> Self {
+        let n_features: usize = basis.dim().into();
+        let n_params = n_features * n_actions;
+
+        LSTDQ {
+            basis,
+            theta: Array2::zeros((n_features, n_actions)),
+
+            policy,
+
+            gamma,
+
+            a: Array2::eye(n_params) * 1e-6,
+            b: Array1::zeros(n_params),
+        }
+    }
+}
+
+impl<B, P> LSTDQ<B, P> {
+    pub fn n_actions(&self) -> usize { self.theta.ncols() }
+
+    pub fn solve(&mut self) {
+        if let Some(theta) = solve(&self.a, &self.b, self.n_actions()) {
+            self.theta.assign(&theta);
+        }
+    }
+
+    /// Discard all accumulated statistics, keeping the current weights.
+    pub fn reset(&mut self) {
+        let n_params = self.b.len();
+
+        self.a = Array2::eye(n_params) * 1e-6;
+        self.b.fill(0.0);
+    }
+
+    pub fn to_lfa<O>(&self, optimiser: O) -> VectorLFA<B, O>
+    where B: Clone,
+    {
+        crate::fa::linear::LFA {
+            basis: self.basis.clone(),
+            weights: self.theta.clone(),
+            optimiser,
+        }
+    }
+
+    pub fn into_lfa<O>(self, optimiser: O) -> VectorLFA<B, O> {
+        crate::fa::linear::LFA {
+            basis: self.basis,
+            weights: self.theta,
+            optimiser,
+        }
+    }
+}
+
+impl<'m, S, B, P> Handler<&'m Batch<S, usize>> for LSTDQ<B, P>
+where
+    B: Basis<&'m S, Value = Features>,
+    P: EnumerablePolicy<&'m S>,
+
+    <P as Function<(&'m S,)>>::Output: Index<usize, Output = f64> + IntoIterator<Item = f64>,
+    <<P as Function<(&'m S,)>>::Output as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    type Response = ();
+    type Error = crate::fa::linear::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, usize>) -> Result<(), Self::Error> {
+        for t in batch {
+            let (s, ns) = t.states();
+            let phi_s = self.basis.project(s)?.into_dense();
+
+            if t.terminated() {
+                accumulate(
+                    &mut self.a,
+                    &mut self.b,
+                    self.gamma,
+                    phi_s,
+                    t.action,
+                    t.reward,
+                    None,
+                );
+            } else {
+                let phi_ns = self.basis.project(ns)?.into_dense();
+                let probs: Vec<f64> = self.policy.evaluate((ns,)).into_iter().collect();
+
+                accumulate(
+                    &mut self.a,
+                    &mut self.b,
+                    self.gamma,
+                    phi_s,
+                    t.action,
+                    t.reward,
+                    Some((phi_ns, &probs)),
+                );
+            }
+        }
+
+        self.solve();
+
+        Ok(())
+    }
+}
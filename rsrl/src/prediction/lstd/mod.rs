@@ -1,14 +1,40 @@
-// TODO: Implement regularized LSTD "http://mlg.eng.cam.ac.uk/hoffmanm/papers/hoffman:2012b.pdf
+// TODO: Implement nested L2/L1 regularized LSTD "http://mlg.eng.cam.ac.uk/hoffmanm/papers/hoffman:2012b.pdf
+use ndarray::Array1;
+
 pub mod ilstd;
 pub mod lambda_lspe;
+pub mod lars_td;
 pub mod lstd;
 pub mod lstd_lambda;
+pub mod lstdq;
 pub mod recursive_lstd;
+pub mod ridge_lstd;
 
 pub use self::{
     ilstd::iLSTD,
     lambda_lspe::LambdaLSPE,
+    lars_td::LARSTD,
     lstd::LSTD,
     lstd_lambda::LSTDLambda,
+    lstdq::LSTDQ,
     recursive_lstd::RecursiveLSTD,
+    ridge_lstd::RidgeLSTD,
 };
+
+/// Solution of a regularised LSTD problem at a given penalty.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct PathPoint {
+    /// Regularisation penalty.
+    pub beta: f64,
+
+    /// Indices of the non-zero weights.
+    pub active: Vec<usize>,
+
+    /// Weight vector at this penalty.
+    pub theta: Array1<f64>,
+}
@@ -7,6 +7,11 @@ use ndarray::{Array1, Array2, Axis};
 use spaces::Space;
 
 #[derive(Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct RecursiveLSTD<B> {
     pub basis: B,
     #[weights]
@@ -0,0 +1,171 @@
+use super::PathPoint;
+use crate::{
+    domains::Batch,
+    fa::linear::{basis::Basis, Features},
+    utils::pinv,
+    Handler,
+    Parameterised,
+};
+use ndarray::{Array1, Array2, Axis};
+use ndarray_linalg::Solve;
+
+/// Least-squares temporal-difference learning with an L2 (ridge) penalty.
+///
+/// # References
+/// - Kolter, J. Z., & Ng, A. Y. (2009). Regularization and feature selection
+/// in least-squares temporal difference learning. In Proceedings of the 26th
+/// International Conference on Machine Learning, pp. 521-528.
+#[derive(Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct RidgeLSTD<B> {
+    pub basis: B,
+    #[weights]
+    pub theta: Array1<f64>,
+
+    pub gamma: f64,
+    pub beta: f64,
+
+    a: Array2<f64>,
+    b: Array1<f64>,
+}
+
+impl<B: spaces::Space> RidgeLSTD<B> {
+    pub fn new(basis: B, gamma: f64, beta: f64) -> Self {
+        let n_features: usize = basis.dim().into();
+
+        RidgeLSTD {
+            basis,
+            theta: Array1::zeros(n_features),
+
+            gamma,
+            beta,
+
+            a: Array2::zeros((n_features, n_features)),
+            b: Array1::zeros(n_features),
+        }
+    }
+}
+
+impl<B> RidgeLSTD<B> {
+    fn solve_with(&self, beta: f64) -> Option<Array1<f64>> {
+        let n_features = self.b.len();
+        let a = &self.a + &(Array2::<f64>::eye(n_features) * beta);
+
+        a.solve(&self.b)
+            .or_else(|_| pinv(&a).map(|ainv| ainv.dot(&self.b)))
+            .ok()
+    }
+
+    pub fn solve(&mut self) {
+        if let Some(theta) = self.solve_with(self.beta) {
+            self.theta.assign(&theta);
+        }
+    }
+
+    /// Compute the regularised solution for each penalty in `betas`, leaving
+    /// the current weights unchanged.
+    pub fn path(&self, betas: &[f64]) -> Vec<PathPoint> {
+        betas
+            .iter()
+            .filter_map(|&beta| {
+                self.solve_with(beta).map(|theta| {
+                    let active = theta
+                        .indexed_iter()
+                        .filter(|(_, w)| w.abs() > 0.0)
+                        .map(|(i, _)| i)
+                        .collect();
+
+                    PathPoint {
+                        beta,
+                        active,
+                        theta,
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+impl<'m, S, A, B> Handler<&'m Batch<S, A>> for RidgeLSTD<B>
+where B: Basis<&'m S, Value = Features>
+{
+    type Response = ();
+    type Error = crate::fa::linear::Error;
+
+    fn handle(&mut self, batch: &'m Batch<S, A>) -> Result<(), Self::Error> {
+        for t in batch {
+            let (s, ns) = t.states();
+
+            let phi_s = self.basis.project(s)?.into_dense();
+
+            self.b.scaled_add(t.reward, &phi_s);
+
+            if t.terminated() {
+                let phi_s = phi_s.insert_axis(Axis(1));
+
+                self.a += &phi_s.view().dot(&phi_s.t());
+            } else {
+                let phi_ns = self.basis.project(ns)?.into_dense();
+                let pd = (self.gamma * phi_ns - &phi_s).insert_axis(Axis(0));
+
+                self.a -= &phi_s.insert_axis(Axis(1)).dot(&pd);
+            }
+        }
+
+        self.solve();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domains::{Observation, Transition},
+        fa::linear::basis::Closure,
+    };
+
+    #[test]
+    fn test_closed_form() {
+        let basis = Closure::new(2, |s: &usize| {
+            let mut phi = Array1::zeros(2);
+            phi[*s] = 1.0;
+
+            Ok(Features::Dense(phi))
+        });
+        let batch = vec![
+            Transition {
+                from: Observation::Full(0),
+                action: (),
+                reward: 0.0,
+                to: Observation::Full(1),
+            },
+            Transition {
+                from: Observation::Full(1),
+                action: (),
+                reward: 1.0,
+                to: Observation::Terminal(0),
+            },
+        ];
+
+        let mut agent = RidgeLSTD::new(basis, 0.9, 0.5);
+
+        agent.handle(&batch).unwrap();
+
+        // A = [[1, -γ], [0, 1]] and b = [0, 1], so θ₁ = 1 / (1 + β) and
+        // θ₀ = γ θ₁ / (1 + β).
+        assert!((agent.theta[1] - 1.0 / 1.5).abs() < 1e-10);
+        assert!((agent.theta[0] - 0.9 / 2.25).abs() < 1e-10);
+
+        let path = agent.path(&[0.0, 1.0]);
+
+        assert!((path[0].theta[0] - 0.9).abs() < 1e-10);
+        assert!((path[1].theta[1] - 0.5).abs() < 1e-10);
+        assert!((agent.theta[1] - 1.0 / 1.5).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    domains::{Observation, Transition},
+    fa::StateUpdate,
+    Function,
+    Handler,
+};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Response<R> {
+    pub td_error: f64,
+    pub rho: f64,
+    pub vfunc_response: R,
+}
+
+/// Differential semi-gradient TD(0) for average-reward prediction.
+///
+/// The average reward per step, `rho`, is tracked with step size `beta`; the
+/// step size of the value update is left to `v_func`.
+///
+/// # References
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.), Section 10.3. MIT Press.
+#[derive(Clone, Debug, Parameterised)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct DifferentialTD<V> {
+    #[weights]
+    pub v_func: V,
+
+    pub beta: f64,
+
+    pub rho: f64,
+}
+
+impl<V> DifferentialTD<V> {
+    pub fn new(v_func: V, beta: f64) -> Self {
+        DifferentialTD {
+            v_func,
+
+            beta,
+
+            rho: 0.0,
+        }
+    }
+}
+
+impl<'m, S, A, V> Handler<&'m Transition<S, A>> for DifferentialTD<V>
+where V: Function<(&'m S,), Output = f64> + Handler<StateUpdate<&'m S, f64>>
+{
+    type Response = Response<V::Response>;
+    type Error = V::Error;
+
+    fn handle(&mut self, transition: &'m Transition<S, A>) -> Result<Self::Response, Self::Error> {
+        let from = transition.from.state();
+        let pred = self.v_func.evaluate((from,));
+
+        let td_error = match transition.to {
+            Observation::Terminal(_) => transition.reward - self.rho - pred,
+            Observation::Full(ref to) | Observation::Partial(ref to) => {
+                transition.reward - self.rho + self.v_func.evaluate((to,)) - pred
+            },
+        };
+
+        self.rho += self.beta * td_error;
+
+        self.v_func
+            .handle(StateUpdate {
+                state: from,
+                error: td_error,
+            })
+            .map(|r| Response {
+                td_error,
+                rho: self.rho,
+                vfunc_response: r,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fa::tabular::Table;
+    use ndarray::Array1;
+
+    #[test]
+    fn test_average_reward() {
+        let mut agent = DifferentialTD::new(Table::dense(Array1::zeros(2)), 0.1);
+
+        // Cycle between two states with rewards 1 and 0.
+        let transitions: Vec<Transition<usize, ()>> = (0..2)
+            .map(|s| Transition {
+                from: Observation::Full(s),
+                action: (),
+                reward: (1 - s) as f64,
+                to: Observation::Full(1 - s),
+            })
+            .collect();
+
+        for i in 0..200 {
+            agent.handle(&transitions[i % 2]).unwrap();
+        }
+
+        assert!((agent.rho - 0.5).abs() < 1e-3);
+    }
+}
@@ -10,6 +10,11 @@ pub mod tdc;
 
 pub use self::{gtd2::GTD2, tdc::TDC};
 
+// Average-reward methods:
+pub mod differential_td;
+
+pub use self::differential_td::DifferentialTD;
+
 // TODO:
 // n-step TD - Sutton & Barto
 // ETD(lambda) - https://arxiv.org/pdf/1503.04269.pdf
@@ -0,0 +1,326 @@
+//! Hyperparameter schedules.
+//!
+//! A schedule produces the current value of a hyperparameter, such as a
+//! learning rate or an exploration parameter, and is advanced either once per
+//! time step or once per episode. Plain `f64` values act as constant
+//! schedules, so any learner or policy that takes a schedule can still be
+//! given a fixed value.
+
+/// Interface for hyperparameter schedules.
+pub trait Schedule {
+    /// Return the current value of the schedule.
+    fn value(&self) -> f64;
+
+    /// Advance the schedule after a single time step.
+    fn step(&mut self) {}
+
+    /// Advance the schedule after the end of an episode.
+    fn end_episode(&mut self) {}
+}
+
+impl Schedule for f64 {
+    fn value(&self) -> f64 { *self }
+}
+
+/// Unit of time with respect to which a schedule is advanced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum Period {
+    /// Advance once per time step.
+    Step,
+
+    /// Advance once per episode.
+    Episode,
+}
+
+macro_rules! impl_schedule {
+    ($type:ty, |$self:ident, $t:ident| $value:expr) => {
+        impl Schedule for $type {
+            fn value(&$self) -> f64 {
+                let $t = $self.t as f64;
+
+                $value
+            }
+
+            fn step(&mut self) {
+                if let Period::Step = self.period {
+                    self.t += 1;
+                }
+            }
+
+            fn end_episode(&mut self) {
+                if let Period::Episode = self.period {
+                    self.t += 1;
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_period_builders {
+    ($type:ident) => {
+        impl $type {
+            /// Advance the schedule once per time step (default).
+            pub fn per_step(self) -> Self {
+                $type {
+                    period: Period::Step,
+                    ..self
+                }
+            }
+
+            /// Advance the schedule once per episode.
+            pub fn per_episode(self) -> Self {
+                $type {
+                    period: Period::Episode,
+                    ..self
+                }
+            }
+
+            /// Reset the schedule to its initial value.
+            pub fn reset(&mut self) { self.t = 0; }
+        }
+    };
+}
+
+/// Constant schedule.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Constant(pub f64);
+
+impl Schedule for Constant {
+    fn value(&self) -> f64 { self.0 }
+}
+
+/// Linear interpolation from `initial` to `target` over `duration` periods,
+/// after which the value is held fixed at `target`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Linear {
+    pub initial: f64,
+    pub target: f64,
+    pub duration: usize,
+
+    pub period: Period,
+    pub t: usize,
+}
+
+impl Linear {
+    pub fn new(initial: f64, target: f64, duration: usize) -> Self {
+        Linear {
+            initial,
+            target,
+            duration,
+
+            period: Period::Step,
+            t: 0,
+        }
+    }
+}
+
+impl_period_builders!(Linear);
+impl_schedule!(Linear, |self, t| {
+    let frac = if self.duration == 0 {
+        1.0
+    } else {
+        (t / self.duration as f64).min(1.0)
+    };
+
+    self.initial + frac * (self.target - self.initial)
+});
+
+/// Exponential decay, `max(initial * rate^t, minimum)`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Exponential {
+    pub initial: f64,
+    pub rate: f64,
+    pub minimum: f64,
+
+    pub period: Period,
+    pub t: usize,
+}
+
+impl Exponential {
+    pub fn new(initial: f64, rate: f64) -> Self {
+        Exponential {
+            initial,
+            rate,
+            minimum: 0.0,
+
+            period: Period::Step,
+            t: 0,
+        }
+    }
+
+    /// Bound the schedule from below by `minimum`.
+    pub fn with_minimum(self, minimum: f64) -> Self { Exponential { minimum, ..self } }
+}
+
+impl_period_builders!(Exponential);
+impl_schedule!(Exponential, |self, t| {
+    (self.initial * self.rate.powf(t)).max(self.minimum)
+});
+
+/// Inverse-time decay, `initial / (1 + rate * t)^power`.
+///
+/// With `power` in (0.5, 1] this satisfies the Robbins-Monro conditions for
+/// stochastic approximation.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct InverseTime {
+    pub initial: f64,
+    pub rate: f64,
+    pub power: f64,
+
+    pub period: Period,
+    pub t: usize,
+}
+
+impl InverseTime {
+    pub fn new(initial: f64, rate: f64) -> Self {
+        InverseTime {
+            initial,
+            rate,
+            power: 1.0,
+
+            period: Period::Step,
+            t: 0,
+        }
+    }
+
+    pub fn with_power(self, power: f64) -> Self { InverseTime { power, ..self } }
+}
+
+impl_period_builders!(InverseTime);
+impl_schedule!(InverseTime, |self, t| {
+    self.initial / (1.0 + self.rate * t).powf(self.power)
+});
+
+/// Piecewise linear schedule through a sequence of `(t, value)` knots.
+///
+/// The value is held fixed before the first knot and after the last.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Piecewise {
+    pub knots: Vec<(usize, f64)>,
+
+    pub period: Period,
+    pub t: usize,
+}
+
+impl Piecewise {
+    pub fn new(mut knots: Vec<(usize, f64)>) -> Self {
+        if knots.is_empty() {
+            panic!("Piecewise schedule requires at least one knot.");
+        }
+
+        knots.sort_by_key(|k| k.0);
+
+        Piecewise {
+            knots,
+
+            period: Period::Step,
+            t: 0,
+        }
+    }
+}
+
+impl_period_builders!(Piecewise);
+impl_schedule!(Piecewise, |self, t| {
+    let i = self.knots.iter().position(|k| k.0 as f64 > t);
+
+    match i {
+        Some(0) => self.knots[0].1,
+        Some(i) => {
+            let (t0, v0) = self.knots[i - 1];
+            let (t1, v1) = self.knots[i];
+
+            v0 + (v1 - v0) * (t - t0 as f64) / (t1 - t0) as f64
+        },
+        None => self.knots[self.knots.len() - 1].1,
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear() {
+        let mut s = Linear::new(1.0, 0.0, 4);
+
+        for &v in [1.0, 0.75, 0.5, 0.25, 0.0, 0.0].iter() {
+            assert!((s.value() - v).abs() < 1e-7);
+
+            s.step();
+        }
+    }
+
+    #[test]
+    fn test_exponential() {
+        let mut s = Exponential::new(1.0, 0.5).with_minimum(0.2);
+
+        for &v in [1.0, 0.5, 0.25, 0.2, 0.2].iter() {
+            assert!((s.value() - v).abs() < 1e-7);
+
+            s.step();
+        }
+    }
+
+    #[test]
+    fn test_inverse_time() {
+        let mut s = InverseTime::new(1.0, 1.0);
+
+        for &v in [1.0, 0.5, 1.0 / 3.0, 0.25].iter() {
+            assert!((s.value() - v).abs() < 1e-7);
+
+            s.step();
+        }
+    }
+
+    #[test]
+    fn test_piecewise() {
+        let mut s = Piecewise::new(vec![(2, 1.0), (4, 0.0), (6, 0.5)]);
+
+        for &v in [1.0, 1.0, 1.0, 0.5, 0.0, 0.25, 0.5, 0.5].iter() {
+            assert!((s.value() - v).abs() < 1e-7);
+
+            s.step();
+        }
+    }
+
+    #[test]
+    fn test_per_episode() {
+        let mut s = Linear::new(1.0, 0.0, 2).per_episode();
+
+        s.step();
+        assert!((s.value() - 1.0).abs() < 1e-7);
+
+        s.end_episode();
+        assert!((s.value() - 0.5).abs() < 1e-7);
+    }
+}
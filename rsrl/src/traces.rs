@@ -3,6 +3,11 @@ use crate::params::{Buffer, BufferMut};
 use ndarray::{ArrayBase, Array, Dimension, IntoDimension, DataMut};
 
 /// Eligibility trace buffer.
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct Trace<B: BufferMut, R: UpdateRule<B>> {
     /// Internal gradient buffer.
     pub buffer: B,
@@ -185,6 +190,11 @@ pub trait UpdateRule<B: BufferMut> {
 }
 
 /// Accumulating eligibility trace rule.
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct Accumulate {
     /// Discount factor.
     pub gamma: f64,
@@ -202,6 +212,11 @@ impl<B: BufferMut> UpdateRule<B> for Accumulate {
 }
 
 /// Replacing (saturating) eligibility trace rule.
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct Saturate {
     /// Discount factor.
     pub gamma: f64,
@@ -220,6 +235,11 @@ impl<B: BufferMut> UpdateRule<B> for Saturate {
 }
 
 /// Dutch eligibility trace rule.
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct Dutch {
     /// Learning rate.
     pub alpha: f64,
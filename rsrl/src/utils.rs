@@ -1,5 +1,6 @@
 #![allow(dead_code)]
-use ndarray::Array2;
+use crate::fa::transforms::{LogSumExp, Transform};
+use ndarray::{Array1, Array2};
 use rand::{seq::SliceRandom, thread_rng, Rng};
 use std::f64;
 
@@ -78,6 +79,20 @@ where
     (maximum, value)
 }
 
+/// Compute the temperature-scaled log-sum-exp, `τ ln Σ exp(x / τ)`, of a set
+/// of values.
+pub fn log_sum_exp(vals: &[f64], tau: f64) -> f64 {
+    let max = vals.iter().fold(f64::MIN, |acc, &v| acc.max(v));
+    let scaled: Array1<f64> = vals.iter().map(|v| (v - max) / tau).collect();
+
+    max + tau * LogSumExp::default().transform(scaled)
+}
+
+/// Compute the mellowmax, `τ ln (Σ exp(x / τ) / n)`, of a set of values.
+pub fn mellowmax(vals: &[f64], tau: f64) -> f64 {
+    log_sum_exp(vals, tau) - tau * (vals.len() as f64).ln()
+}
+
 /// Compute the pseudo-inverse of a real matrix using SVD.
 pub fn pinv(m: &Array2<f64>) -> Result<Array2<f64>, ndarray_linalg::error::LinalgError> {
     use ndarray::Axis;
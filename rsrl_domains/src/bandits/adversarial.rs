@@ -0,0 +1,88 @@
+use super::Bandit;
+use crate::{spaces::discrete::Ordinal, Domain, Observation, Reward};
+
+/// Bandit with rewards chosen by an oblivious adversary.
+///
+/// The adversary fixes a sequence of reward vectors, `rewards[t][i]`, ahead of
+/// time; the sequence is repeated once exhausted. Rewards are deterministic
+/// given the time step, but no arm need be best throughout, so stochastic
+/// bandit algorithms can be made to suffer linear regret.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Adversarial {
+    rewards: Vec<Vec<f64>>,
+    t: usize,
+}
+
+impl Adversarial {
+    pub fn new(rewards: Vec<Vec<f64>>) -> Self {
+        let n_arms = rewards.first().map(|r| r.len()).unwrap_or(0);
+
+        if n_arms == 0 || rewards.iter().any(|r| r.len() != n_arms) {
+            panic!("Adversarial bandit rewards must be non-empty with one entry per arm.");
+        }
+
+        Adversarial { rewards, t: 0 }
+    }
+
+    /// Construct a two-armed bandit in which the better arm alternates every
+    /// `period` steps.
+    pub fn alternating(period: usize) -> Self {
+        let period = period.max(1);
+        let rewards = (0..2 * period)
+            .map(|t| if t < period { vec![1.0, 0.0] } else { vec![0.0, 1.0] })
+            .collect();
+
+        Adversarial::new(rewards)
+    }
+}
+
+impl Default for Adversarial {
+    fn default() -> Adversarial { Adversarial::alternating(100) }
+}
+
+impl Domain for Adversarial {
+    type StateSpace = Ordinal;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<usize> { Observation::Full(0) }
+
+    fn step(&mut self, action: &usize) -> (Observation<usize>, Reward) {
+        let reward = self.rewards[self.t][*action];
+
+        self.t = (self.t + 1) % self.rewards.len();
+
+        (self.emit(), reward)
+    }
+
+    fn state_space(&self) -> Ordinal { Ordinal::new(1) }
+
+    fn action_space(&self) -> Ordinal { Ordinal::new(self.rewards[0].len()) }
+}
+
+impl Bandit for Adversarial {
+    fn n_arms(&self) -> usize { self.rewards[0].len() }
+
+    fn expected_rewards(&self) -> Vec<f64> { self.rewards[self.t].clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alternating() {
+        let mut b = Adversarial::alternating(2);
+
+        assert_eq!(b.step(&0).1, 1.0);
+        assert_eq!(b.step(&0).1, 1.0);
+        assert_eq!(b.step(&0).1, 0.0);
+        assert_eq!(b.expected_rewards(), vec![0.0, 1.0]);
+        assert_eq!(b.step(&1).1, 1.0);
+        assert_eq!(b.step(&1).1, 0.0);
+    }
+}
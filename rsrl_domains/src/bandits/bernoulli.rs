@@ -0,0 +1,89 @@
+use super::Bandit;
+use crate::{spaces::discrete::Ordinal, Domain, Observation, Reward};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Stationary bandit with Bernoulli-distributed rewards.
+///
+/// Pulling arm `i` yields a reward of `1` with probability `probs[i]`, and `0`
+/// otherwise.
+#[derive(Debug)]
+pub struct Bernoulli {
+    probs: Vec<f64>,
+
+    rng: StdRng,
+}
+
+impl Bernoulli {
+    pub fn new(probs: Vec<f64>) -> Self {
+        if probs.iter().any(|p| *p < 0.0 || *p > 1.0) {
+            panic!("Bernoulli bandit probabilities must lie in [0, 1].");
+        }
+
+        Bernoulli {
+            probs,
+
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Seed the random number generator used to draw rewards.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Bernoulli {
+            rng: StdRng::seed_from_u64(seed),
+            ..self
+        }
+    }
+}
+
+impl Default for Bernoulli {
+    fn default() -> Bernoulli { Bernoulli::new(vec![0.1, 0.5, 0.9]) }
+}
+
+impl Domain for Bernoulli {
+    type StateSpace = Ordinal;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<usize> { Observation::Full(0) }
+
+    fn step(&mut self, action: &usize) -> (Observation<usize>, Reward) {
+        let reward = if self.rng.gen_bool(self.probs[*action]) {
+            1.0
+        } else {
+            0.0
+        };
+
+        (self.emit(), reward)
+    }
+
+    fn state_space(&self) -> Ordinal { Ordinal::new(1) }
+
+    fn action_space(&self) -> Ordinal { Ordinal::new(self.probs.len()) }
+}
+
+impl Bandit for Bernoulli {
+    fn n_arms(&self) -> usize { self.probs.len() }
+
+    fn expected_rewards(&self) -> Vec<f64> { self.probs.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewards() {
+        let mut b = Bernoulli::new(vec![0.0, 1.0]);
+
+        for _ in 0..10 {
+            assert_eq!(b.step(&0).1, 0.0);
+            assert_eq!(b.step(&1).1, 1.0);
+        }
+
+        assert!(!b.emit().is_terminal());
+        assert_eq!(b.expected_rewards(), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_probs() { Bernoulli::new(vec![0.5, 1.5]); }
+}
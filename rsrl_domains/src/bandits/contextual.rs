@@ -0,0 +1,236 @@
+use super::ContextualBandit;
+use crate::{
+    spaces::{discrete::Ordinal, real::Interval, ProductSpace},
+    Domain,
+    Observation,
+    Reward,
+};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use rand_distr::{Distribution, StandardNormal};
+
+fn random_context<R: Rng + ?Sized>(rng: &mut R, dim: usize) -> Vec<f64> {
+    (0..dim).map(|_| rng.gen_range(-1.0, 1.0)).collect()
+}
+
+fn random_weights<R: Rng + ?Sized>(rng: &mut R, n_arms: usize, dim: usize) -> Vec<Vec<f64>> {
+    let scale = (dim as f64).sqrt().recip();
+
+    (0..n_arms)
+        .map(|_| {
+            (0..dim)
+                .map(|_| {
+                    let z: f64 = StandardNormal.sample(rng);
+
+                    scale * z
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn context_space(dim: usize) -> ProductSpace<Interval> {
+    ProductSpace::new(vec![Interval::bounded(-1.0, 1.0); dim])
+}
+
+fn inner(x: &[f64], y: &[f64]) -> f64 { x.iter().zip(y.iter()).map(|(a, b)| a * b).sum() }
+
+/// Contextual bandit with rewards drawn from a hidden linear model.
+///
+/// At every step a context, `x`, is drawn uniformly from `[-1, 1]^d` and
+/// emitted as the state. Pulling arm `i` then yields a reward of
+/// `weights[i]·x + noise * z`, where `z` is standard normal.
+#[derive(Debug)]
+pub struct LinearContextual {
+    weights: Vec<Vec<f64>>,
+    noise: f64,
+
+    context: Vec<f64>,
+    rng: StdRng,
+}
+
+impl LinearContextual {
+    pub fn new(weights: Vec<Vec<f64>>, noise: f64) -> Self {
+        let dim = weights.first().map_or(0, |w| w.len());
+
+        if weights.iter().any(|w| w.len() != dim) {
+            panic!("LinearContextual bandit requires weights of equal dimension for each arm.");
+        }
+
+        let mut rng = StdRng::from_entropy();
+
+        LinearContextual {
+            context: random_context(&mut rng, dim),
+            weights,
+            noise,
+
+            rng,
+        }
+    }
+
+    /// Seed the random number generator used to draw contexts and rewards.
+    ///
+    /// The current context is redrawn from the seeded generator.
+    pub fn with_seed(self, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        LinearContextual {
+            context: random_context(&mut rng, self.context.len()),
+            rng,
+            ..self
+        }
+    }
+
+    /// Construct a bandit with weights drawn from `N(0, I / dim)`.
+    pub fn random(n_arms: usize, dim: usize, noise: f64) -> Self {
+        let weights = random_weights(&mut thread_rng(), n_arms, dim);
+
+        LinearContextual::new(weights, noise)
+    }
+}
+
+impl Default for LinearContextual {
+    fn default() -> LinearContextual { LinearContextual::random(5, 5, 0.1) }
+}
+
+impl Domain for LinearContextual {
+    type StateSpace = ProductSpace<Interval>;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<Vec<f64>> { Observation::Full(self.context.clone()) }
+
+    fn step(&mut self, action: &usize) -> (Observation<Vec<f64>>, Reward) {
+        let z: f64 = StandardNormal.sample(&mut self.rng);
+        let reward = inner(&self.weights[*action], &self.context) + self.noise * z;
+
+        self.context = random_context(&mut self.rng, self.context.len());
+
+        (self.emit(), reward)
+    }
+
+    fn state_space(&self) -> Self::StateSpace { context_space(self.context.len()) }
+
+    fn action_space(&self) -> Ordinal { Ordinal::new(self.weights.len()) }
+}
+
+impl ContextualBandit for LinearContextual {
+    fn n_arms(&self) -> usize { self.weights.len() }
+
+    fn expected_rewards(&self) -> Vec<f64> {
+        self.weights.iter().map(|w| inner(w, &self.context)).collect()
+    }
+}
+
+/// Contextual bandit with Bernoulli rewards drawn from a hidden logistic
+/// model.
+///
+/// Contexts are generated as in `LinearContextual`, but pulling arm `i` yields
+/// a reward of `1` with probability `σ(weights[i]·x)`, and `0` otherwise.
+#[derive(Debug)]
+pub struct LogisticContextual {
+    weights: Vec<Vec<f64>>,
+
+    context: Vec<f64>,
+    rng: StdRng,
+}
+
+impl LogisticContextual {
+    pub fn new(weights: Vec<Vec<f64>>) -> Self {
+        let dim = weights.first().map_or(0, |w| w.len());
+
+        if weights.iter().any(|w| w.len() != dim) {
+            panic!("LogisticContextual bandit requires weights of equal dimension for each arm.");
+        }
+
+        let mut rng = StdRng::from_entropy();
+
+        LogisticContextual {
+            context: random_context(&mut rng, dim),
+            weights,
+
+            rng,
+        }
+    }
+
+    /// Seed the random number generator used to draw contexts and rewards.
+    ///
+    /// The current context is redrawn from the seeded generator.
+    pub fn with_seed(self, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        LogisticContextual {
+            context: random_context(&mut rng, self.context.len()),
+            rng,
+            ..self
+        }
+    }
+
+    /// Construct a bandit with weights drawn from `N(0, I / dim)`.
+    pub fn random(n_arms: usize, dim: usize) -> Self {
+        LogisticContextual::new(random_weights(&mut thread_rng(), n_arms, dim))
+    }
+}
+
+impl Default for LogisticContextual {
+    fn default() -> LogisticContextual { LogisticContextual::random(5, 5) }
+}
+
+impl Domain for LogisticContextual {
+    type StateSpace = ProductSpace<Interval>;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<Vec<f64>> { Observation::Full(self.context.clone()) }
+
+    fn step(&mut self, action: &usize) -> (Observation<Vec<f64>>, Reward) {
+        let p = self.expected_rewards()[*action];
+        let reward = if self.rng.gen_bool(p) { 1.0 } else { 0.0 };
+
+        self.context = random_context(&mut self.rng, self.context.len());
+
+        (self.emit(), reward)
+    }
+
+    fn state_space(&self) -> Self::StateSpace { context_space(self.context.len()) }
+
+    fn action_space(&self) -> Ordinal { Ordinal::new(self.weights.len()) }
+}
+
+impl ContextualBandit for LogisticContextual {
+    fn n_arms(&self) -> usize { self.weights.len() }
+
+    fn expected_rewards(&self) -> Vec<f64> {
+        self.weights
+            .iter()
+            .map(|w| 1.0 / (1.0 + (-inner(w, &self.context)).exp()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_rewards() {
+        let mut b = LinearContextual::new(vec![vec![1.0, 0.0], vec![0.0, -2.0]], 0.0);
+
+        for _ in 0..10 {
+            let x = b.emit().state().clone();
+
+            assert!(x.iter().all(|v| *v >= -1.0 && *v <= 1.0));
+            assert_eq!(b.expected_rewards(), vec![x[0], -2.0 * x[1]]);
+            assert_eq!(b.step(&1).1, -2.0 * x[1]);
+        }
+    }
+
+    #[test]
+    fn test_logistic_rewards() {
+        let mut b = LogisticContextual::new(vec![vec![0.0; 3]]);
+
+        assert_eq!(b.expected_rewards(), vec![0.5]);
+        assert!((0..10).all(|_| {
+            let r = b.step(&0).1;
+
+            r == 0.0 || r == 1.0
+        }));
+    }
+}
@@ -0,0 +1,98 @@
+use super::Bandit;
+use crate::{spaces::discrete::Ordinal, Domain, Observation, Reward};
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+
+/// Non-stationary Gaussian bandit whose arm means follow random walks.
+///
+/// After every pull, the mean of each arm is perturbed by independent Gaussian
+/// noise with standard deviation `drift`, so the identity of the best arm
+/// changes over time.
+#[derive(Debug)]
+pub struct Drifting {
+    means: Vec<f64>,
+    stddev: f64,
+    drift: f64,
+
+    rng: StdRng,
+}
+
+impl Drifting {
+    pub fn new(means: Vec<f64>, stddev: f64, drift: f64) -> Self {
+        if stddev < 0.0 || drift < 0.0 {
+            panic!("Drifting bandit noise parameters must be non-negative.");
+        }
+
+        Drifting {
+            means,
+            stddev,
+            drift,
+
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Seed the random number generator used to draw rewards.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Drifting {
+            rng: StdRng::seed_from_u64(seed),
+            ..self
+        }
+    }
+}
+
+impl Default for Drifting {
+    fn default() -> Drifting { Drifting::new(vec![0.0; 10], 1.0, 0.01) }
+}
+
+impl Domain for Drifting {
+    type StateSpace = Ordinal;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<usize> { Observation::Full(0) }
+
+    fn step(&mut self, action: &usize) -> (Observation<usize>, Reward) {
+        let noise = Normal::new(0.0, self.stddev).unwrap();
+        let reward = self.means[*action] + noise.sample(&mut self.rng);
+
+        let walk = Normal::new(0.0, self.drift).unwrap();
+
+        for m in self.means.iter_mut() {
+            *m += walk.sample(&mut self.rng);
+        }
+
+        (self.emit(), reward)
+    }
+
+    fn state_space(&self) -> Ordinal { Ordinal::new(1) }
+
+    fn action_space(&self) -> Ordinal { Ordinal::new(self.means.len()) }
+}
+
+impl Bandit for Drifting {
+    fn n_arms(&self) -> usize { self.means.len() }
+
+    fn expected_rewards(&self) -> Vec<f64> { self.means.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drift() {
+        let mut b = Drifting::new(vec![0.0, 0.0], 0.0, 1.0);
+
+        b.step(&0);
+
+        assert!(b.expected_rewards().iter().all(|m| *m != 0.0));
+    }
+
+    #[test]
+    fn test_stationary() {
+        let mut b = Drifting::new(vec![1.0, 2.0], 0.0, 0.0);
+
+        assert_eq!(b.step(&1).1, 2.0);
+        assert_eq!(b.expected_rewards(), vec![1.0, 2.0]);
+    }
+}
@@ -0,0 +1,93 @@
+use super::Bandit;
+use crate::{spaces::discrete::Ordinal, Domain, Observation, Reward};
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::{Distribution, StandardNormal};
+
+/// Stationary bandit with Gaussian-distributed rewards.
+#[derive(Debug)]
+pub struct Gaussian {
+    means: Vec<f64>,
+    stddevs: Vec<f64>,
+
+    rng: StdRng,
+}
+
+impl Gaussian {
+    pub fn new(means: Vec<f64>, stddevs: Vec<f64>) -> Self {
+        if means.len() != stddevs.len() || stddevs.iter().any(|s| *s < 0.0) {
+            panic!("Gaussian bandit requires one non-negative standard deviation per arm.");
+        }
+
+        Gaussian {
+            means,
+            stddevs,
+
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Seed the random number generator used to draw rewards.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Gaussian {
+            rng: StdRng::seed_from_u64(seed),
+            ..self
+        }
+    }
+
+    /// Construct a bandit whose arms share a common standard deviation.
+    pub fn homoscedastic(means: Vec<f64>, stddev: f64) -> Self {
+        let stddevs = vec![stddev; means.len()];
+
+        Gaussian::new(means, stddevs)
+    }
+}
+
+impl Default for Gaussian {
+    fn default() -> Gaussian { Gaussian::homoscedastic(vec![0.0, 0.5, 1.0], 1.0) }
+}
+
+impl Domain for Gaussian {
+    type StateSpace = Ordinal;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<usize> { Observation::Full(0) }
+
+    fn step(&mut self, action: &usize) -> (Observation<usize>, Reward) {
+        let z: f64 = StandardNormal.sample(&mut self.rng);
+        let reward = self.means[*action] + self.stddevs[*action] * z;
+
+        (self.emit(), reward)
+    }
+
+    fn state_space(&self) -> Ordinal { Ordinal::new(1) }
+
+    fn action_space(&self) -> Ordinal { Ordinal::new(self.means.len()) }
+}
+
+impl Bandit for Gaussian {
+    fn n_arms(&self) -> usize { self.means.len() }
+
+    fn expected_rewards(&self) -> Vec<f64> { self.means.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mean_reward() {
+        let mut b = Gaussian::homoscedastic(vec![-1.0, 2.0], 0.1);
+        let mean = (0..1000).map(|_| b.step(&1).1).sum::<f64>() / 1000.0;
+
+        assert!((mean - 2.0).abs() < 0.05);
+        assert_eq!(b.expected_rewards(), vec![-1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_seed() {
+        let mut b1 = Gaussian::default().with_seed(0);
+        let mut b2 = Gaussian::default().with_seed(0);
+
+        assert!((0..10).all(|_| b1.step(&2).1 == b2.step(&2).1));
+    }
+}
@@ -0,0 +1,108 @@
+//! Multi-armed bandit domains.
+//!
+//! Each bandit is a continuing `Domain` with a single state, `0`, and one
+//! action per arm; contextual bandits instead emit a fresh context as the
+//! state at every step. Since the expected reward of every arm is known to the
+//! domain, the performance of an agent can be measured exactly with `Regret`.
+use crate::{spaces::discrete::Ordinal, Domain};
+
+mod adversarial;
+mod bernoulli;
+mod contextual;
+mod drifting;
+mod gaussian;
+
+pub use self::adversarial::Adversarial;
+pub use self::bernoulli::Bernoulli;
+pub use self::contextual::{LinearContextual, LogisticContextual};
+pub use self::drifting::Drifting;
+pub use self::gaussian::Gaussian;
+
+/// Interface for multi-armed bandit domains.
+pub trait Bandit: Domain<StateSpace = Ordinal, ActionSpace = Ordinal> {
+    /// Return the number of arms.
+    fn n_arms(&self) -> usize;
+
+    /// Return the expected reward of each arm at the current time step.
+    fn expected_rewards(&self) -> Vec<f64>;
+}
+
+/// Interface for contextual bandit domains.
+pub trait ContextualBandit: Domain<ActionSpace = Ordinal> {
+    /// Return the number of arms.
+    fn n_arms(&self) -> usize;
+
+    /// Return the expected reward of each arm given the current context.
+    fn expected_rewards(&self) -> Vec<f64>;
+}
+
+/// Cumulative regret of a sequence of arm pulls.
+///
+/// The expected rewards of all arms must be recorded *before* each pull, since
+/// they may change over time. Two notions of regret are tracked: `regret`,
+/// measured against the best fixed arm in hindsight, which coincides with the
+/// pseudo-regret for stationary bandits; and `dynamic_regret`, measured
+/// against the best arm at every time step.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Regret {
+    n_pulls: usize,
+    received: f64,
+    dynamic: f64,
+    totals: Vec<f64>,
+}
+
+impl Regret {
+    pub fn new() -> Self { Regret::default() }
+
+    /// Record the pull of `arm` given the current `expected` rewards.
+    pub fn record(&mut self, expected: &[f64], arm: usize) {
+        if self.totals.len() < expected.len() {
+            self.totals.resize(expected.len(), 0.0);
+        }
+
+        let best = expected.iter().fold(f64::MIN, |acc, &r| acc.max(r));
+
+        self.n_pulls += 1;
+        self.received += expected[arm];
+        self.dynamic += best - expected[arm];
+
+        for (t, r) in self.totals.iter_mut().zip(expected.iter()) {
+            *t += r;
+        }
+    }
+
+    /// Return the number of recorded pulls.
+    pub fn n_pulls(&self) -> usize { self.n_pulls }
+
+    /// Return the regret with respect to the best fixed arm in hindsight.
+    pub fn regret(&self) -> f64 {
+        self.totals.iter().fold(f64::MIN, |acc, &t| acc.max(t)).max(self.received)
+            - self.received
+    }
+
+    /// Return the regret with respect to the best arm at each time step.
+    pub fn dynamic_regret(&self) -> f64 { self.dynamic }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regret() {
+        let mut regret = Regret::new();
+
+        regret.record(&[0.2, 0.8], 0);
+        regret.record(&[0.2, 0.8], 1);
+        regret.record(&[0.9, 0.1], 1);
+
+        assert_eq!(regret.n_pulls(), 3);
+        assert!((regret.regret() - 0.6).abs() < 1e-10);
+        assert!((regret.dynamic_regret() - 1.4).abs() < 1e-10);
+    }
+}
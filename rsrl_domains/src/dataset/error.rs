@@ -0,0 +1,60 @@
+use std::{error, fmt, io};
+
+/// Error type for reading and writing datasets.
+#[derive(Debug)]
+pub enum Error {
+    /// Underlying I/O failure.
+    Io(io::Error),
+
+    /// Failure encoding or decoding a line-delimited JSON record.
+    Json(serde_json::Error),
+
+    /// Failure encoding or decoding a binary record.
+    Binary(bincode::Error),
+
+    /// The stream does not start with a valid dataset header.
+    InvalidHeader,
+
+    /// The dataset was written with a newer, unsupported format version.
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(ref e) => write!(f, "I/O error: {}", e),
+            Error::Json(ref e) => write!(f, "JSON error: {}", e),
+            Error::Binary(ref e) => write!(f, "binary encoding error: {}", e),
+            Error::InvalidHeader => write!(f, "invalid dataset header"),
+            Error::UnsupportedVersion(v) => write!(
+                f,
+                "unsupported dataset version {} (latest supported is {})",
+                v,
+                super::VERSION
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(ref e) => Some(e),
+            Error::Json(ref e) => Some(e),
+            Error::Binary(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error { Error::Io(e) }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Error { Error::Json(e) }
+}
+
+impl From<bincode::Error> for Error {
+    fn from(e: bincode::Error) -> Error { Error::Binary(e) }
+}
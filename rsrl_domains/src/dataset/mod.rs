@@ -0,0 +1,559 @@
+//! Versioned on-disk format for recorded trajectories.
+//!
+//! A dataset consists of a header, holding the format version and a
+//! `Metadata` record, followed by a stream of `Episode`s. Two encodings are
+//! supported:
+//!
+//! - `Format::Json`: line-delimited JSON, with the header on the first line
+//! and one episode per subsequent line;
+//! - `Format::Binary`: the magic bytes `RSRL` and a little-endian `u32`
+//! version, followed by length-prefixed `bincode` records.
+//!
+//! Readers detect the encoding automatically.
+use crate::{Action, Batch, Domain, State, Trajectory, Transition};
+use serde_crate::{de::DeserializeOwned, Serialize};
+use spaces::Space;
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    marker::PhantomData,
+    path::Path,
+};
+
+mod error;
+
+pub use self::error::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Latest version of the dataset format.
+pub const VERSION: u32 = 1;
+
+const MAGIC: &[u8; 4] = b"RSRL";
+const MAX_FRAME_LEN: u64 = 1 << 30;
+const JSON_FORMAT_NAME: &str = "rsrl-dataset";
+
+/// Encoding used for a dataset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Binary,
+}
+
+/// Description of the data-generating process.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct Metadata {
+    /// Name of the domain the data was collected from.
+    pub domain: String,
+
+    /// Description of the domain's state space.
+    pub state_space: String,
+
+    /// Description of the domain's action space.
+    pub action_space: String,
+
+    /// Description of the behaviour policy, if known.
+    pub behaviour: Option<String>,
+}
+
+impl Metadata {
+    pub fn new<D>(name: impl Into<String>, domain: &D) -> Self
+    where
+        D: Domain,
+        D::StateSpace: Display,
+        D::ActionSpace: Display,
+    {
+        Metadata {
+            domain: name.into(),
+            state_space: domain.state_space().to_string(),
+            action_space: domain.action_space().to_string(),
+            behaviour: None,
+        }
+    }
+
+    pub fn with_behaviour(mut self, behaviour: impl Into<String>) -> Self {
+        self.behaviour = Some(behaviour.into());
+
+        self
+    }
+}
+
+/// A recorded trajectory together with the behaviour policy's probability
+/// (or density) of each action taken.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct Episode<S, A> {
+    pub trajectory: Trajectory<S, A>,
+    pub probabilities: Option<Vec<f64>>,
+}
+
+impl<S, A> From<Trajectory<S, A>> for Episode<S, A> {
+    fn from(trajectory: Trajectory<S, A>) -> Episode<S, A> {
+        Episode {
+            trajectory,
+            probabilities: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+struct JsonHeader {
+    format: String,
+    version: u32,
+    metadata: Metadata,
+}
+
+/// Streaming writer for datasets.
+///
+/// Episodes can be written whole, recorded directly from
+/// `Domain::rollout`, or assembled transition-by-transition during
+/// training. Any partially recorded episode is written out by `finish`.
+pub struct Writer<W: Write, S, A> {
+    inner: W,
+    format: Format,
+
+    pending: Option<Episode<S, A>>,
+}
+
+impl<S, A> Writer<BufWriter<File>, S, A> {
+    /// Create a new dataset file at `path`.
+    pub fn create<P: AsRef<Path>>(path: P, format: Format, metadata: &Metadata) -> Result<Self> {
+        Writer::new(BufWriter::new(File::create(path)?), format, metadata)
+    }
+}
+
+impl<W: Write, S, A> Writer<W, S, A> {
+    pub fn new(mut inner: W, format: Format, metadata: &Metadata) -> Result<Self> {
+        match format {
+            Format::Json => {
+                serde_json::to_writer(&mut inner, &JsonHeader {
+                    format: JSON_FORMAT_NAME.to_owned(),
+                    version: VERSION,
+                    metadata: metadata.clone(),
+                })?;
+                inner.write_all(b"\n")?;
+            },
+            Format::Binary => {
+                inner.write_all(MAGIC)?;
+                inner.write_all(&VERSION.to_le_bytes())?;
+
+                write_frame(&mut inner, metadata)?;
+            },
+        }
+
+        Ok(Writer {
+            inner,
+            format,
+
+            pending: None,
+        })
+    }
+
+    /// Write a complete episode.
+    pub fn write(&mut self, episode: &Episode<S, A>) -> Result<()>
+    where
+        S: Serialize,
+        A: Serialize,
+    {
+        match self.format {
+            Format::Json => {
+                serde_json::to_writer(&mut self.inner, episode)?;
+                self.inner.write_all(b"\n")?;
+            },
+            Format::Binary => write_frame(&mut self.inner, episode)?,
+        }
+
+        Ok(())
+    }
+
+    /// Write a trajectory without behaviour-policy probabilities.
+    pub fn write_trajectory(&mut self, trajectory: Trajectory<S, A>) -> Result<()>
+    where
+        S: Serialize,
+        A: Serialize,
+    {
+        self.write(&Episode::from(trajectory))
+    }
+
+    /// Roll out `domain` under a policy returning each action along with its
+    /// probability, write the resulting episode and return the trajectory.
+    pub fn record<D, F>(
+        &mut self,
+        domain: D,
+        mut pi: F,
+        step_limit: Option<usize>,
+    ) -> Result<Trajectory<S, A>>
+    where
+        D: Domain,
+        D::StateSpace: Space<Value = S>,
+        D::ActionSpace: Space<Value = A>,
+        F: FnMut(&State<D>) -> (Action<D>, f64),
+        S: Serialize,
+        A: Serialize,
+    {
+        let mut probabilities = vec![];
+        let trajectory = domain.rollout(
+            |s| {
+                let (a, p) = pi(s);
+
+                probabilities.push(p);
+
+                a
+            },
+            step_limit,
+        );
+
+        probabilities.truncate(trajectory.n_transitions());
+
+        let episode = Episode {
+            trajectory,
+            probabilities: Some(probabilities),
+        };
+
+        self.write(&episode)?;
+
+        Ok(episode.trajectory)
+    }
+
+    /// Append a transition to the current episode, along with the behaviour
+    /// policy's probability of the action taken. The episode is written out
+    /// once a terminal transition is pushed.
+    pub fn push(&mut self, transition: Transition<S, A>, probability: Option<f64>) -> Result<()>
+    where
+        S: Serialize,
+        A: Serialize,
+    {
+        let terminated = transition.terminated();
+        let Transition {
+            from,
+            action,
+            reward,
+            to,
+        } = transition;
+
+        let episode = self.pending.get_or_insert_with(|| Episode {
+            trajectory: Trajectory {
+                start: from,
+                steps: vec![],
+            },
+            probabilities: Some(vec![]),
+        });
+
+        episode.trajectory.steps.push((to, action, reward));
+
+        match probability {
+            Some(p) => {
+                if let Some(ref mut ps) = episode.probabilities {
+                    ps.push(p);
+                }
+            },
+            None => episode.probabilities = None,
+        }
+
+        if terminated {
+            self.end_episode()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Write out the current episode, if any, e.g. after a step limit.
+    pub fn end_episode(&mut self) -> Result<()>
+    where
+        S: Serialize,
+        A: Serialize,
+    {
+        match self.pending.take() {
+            Some(episode) => self.write(&episode),
+            None => Ok(()),
+        }
+    }
+
+    /// Write out any pending episode, flush the stream and return it.
+    pub fn finish(mut self) -> Result<W>
+    where
+        S: Serialize,
+        A: Serialize,
+    {
+        self.end_episode()?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+}
+
+/// Streaming reader for datasets, yielding one `Episode` at a time.
+pub struct Reader<R: BufRead, S, A> {
+    inner: R,
+    format: Format,
+
+    version: u32,
+    metadata: Metadata,
+
+    _marker: PhantomData<(S, A)>,
+}
+
+impl<S, A> Reader<BufReader<File>, S, A> {
+    /// Open the dataset file at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Reader::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: BufRead, S, A> Reader<R, S, A> {
+    pub fn new(mut inner: R) -> Result<Self> {
+        let is_binary = inner.fill_buf()?.starts_with(MAGIC);
+
+        let (format, version, metadata) = if is_binary {
+            let mut header = [0u8; 8];
+
+            inner.read_exact(&mut header)?;
+
+            let mut version = [0u8; 4];
+            version.copy_from_slice(&header[4..]);
+
+            let version = check_version(u32::from_le_bytes(version))?;
+            let metadata = read_frame(&mut inner)?.ok_or(Error::InvalidHeader)?;
+
+            (Format::Binary, version, metadata)
+        } else {
+            let mut line = String::new();
+
+            inner.read_line(&mut line)?;
+
+            let header: JsonHeader =
+                serde_json::from_str(&line).map_err(|_| Error::InvalidHeader)?;
+
+            if header.format != JSON_FORMAT_NAME {
+                return Err(Error::InvalidHeader);
+            }
+
+            (Format::Json, check_version(header.version)?, header.metadata)
+        };
+
+        Ok(Reader {
+            inner,
+            format,
+
+            version,
+            metadata,
+
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn format(&self) -> Format { self.format }
+
+    pub fn version(&self) -> u32 { self.version }
+
+    pub fn metadata(&self) -> &Metadata { &self.metadata }
+
+    /// Stream the episodes as trajectories, discarding probabilities.
+    pub fn trajectories(self) -> impl Iterator<Item = Result<Trajectory<S, A>>>
+    where
+        S: DeserializeOwned,
+        A: DeserializeOwned,
+    {
+        self.map(|e| e.map(|e| e.trajectory))
+    }
+
+    /// Read all remaining episodes into a single batch of transitions.
+    pub fn into_batch(self) -> Result<Batch<S, A>>
+    where
+        S: Clone + DeserializeOwned,
+        A: Clone + DeserializeOwned,
+    {
+        let mut batch = vec![];
+
+        for trajectory in self.trajectories() {
+            let trajectory = trajectory?;
+
+            if trajectory.n_transitions() > 0 {
+                batch.extend(trajectory.into_batch());
+            }
+        }
+
+        Ok(batch)
+    }
+}
+
+impl<R: BufRead, S, A> Iterator for Reader<R, S, A>
+where
+    S: DeserializeOwned,
+    A: DeserializeOwned,
+{
+    type Item = Result<Episode<S, A>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.format {
+            Format::Json => loop {
+                let mut line = String::new();
+
+                match self.inner.read_line(&mut line) {
+                    Ok(0) => return None,
+                    Ok(_) if line.trim().is_empty() => continue,
+                    Ok(_) => return Some(serde_json::from_str(&line).map_err(Error::from)),
+                    Err(e) => return Some(Err(e.into())),
+                }
+            },
+            Format::Binary => read_frame(&mut self.inner).transpose(),
+        }
+    }
+}
+
+fn check_version(version: u32) -> Result<u32> {
+    if version > VERSION {
+        Err(Error::UnsupportedVersion(version))
+    } else {
+        Ok(version)
+    }
+}
+
+fn write_frame<W: Write, T: Serialize>(writer: &mut W, value: &T) -> Result<()> {
+    let bytes = bincode::serialize(value)?;
+
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+
+    Ok(())
+}
+
+fn read_frame<R: BufRead, T: DeserializeOwned>(reader: &mut R) -> Result<Option<T>> {
+    if reader.fill_buf()?.is_empty() {
+        return Ok(None);
+    }
+
+    let mut len = [0u8; 8];
+
+    reader.read_exact(&mut len)?;
+
+    // Reject corrupt or malicious length prefixes before allocating.
+    let len = u64::from_le_bytes(len);
+
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame length {} exceeds the maximum of {} bytes", len, MAX_FRAME_LEN),
+        )
+        .into());
+    }
+
+    let mut bytes = vec![0u8; len as usize];
+
+    reader.read_exact(&mut bytes)?;
+
+    Ok(Some(bincode::deserialize(&bytes)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Observation, Roulette};
+    use std::io::Cursor;
+
+    fn episode() -> Episode<usize, usize> {
+        Episode {
+            trajectory: Trajectory {
+                start: Observation::Full(0),
+                steps: vec![
+                    (Observation::Full(1), 1, 0.5),
+                    (Observation::Terminal(2), 0, -1.0),
+                ],
+            },
+            probabilities: Some(vec![0.25, 0.75]),
+        }
+    }
+
+    fn round_trip(format: Format) {
+        let metadata = Metadata::new("Roulette", &Roulette::default()).with_behaviour("random");
+
+        let mut writer = Writer::new(vec![], format, &metadata).unwrap();
+
+        writer.write(&episode()).unwrap();
+        writer
+            .push(
+                Transition {
+                    from: Observation::Full(0),
+                    action: 1,
+                    reward: 0.5,
+                    to: Observation::Terminal(1),
+                },
+                None,
+            )
+            .unwrap();
+
+        let bytes = writer.finish().unwrap();
+        let reader: Reader<_, usize, usize> = Reader::new(Cursor::new(bytes)).unwrap();
+
+        assert_eq!(reader.format(), format);
+        assert_eq!(reader.version(), VERSION);
+        assert_eq!(reader.metadata(), &metadata);
+
+        let episodes: Vec<_> = reader.map(|e| e.unwrap()).collect();
+
+        assert_eq!(episodes.len(), 2);
+        assert_eq!(episodes[0].probabilities, Some(vec![0.25, 0.75]));
+        assert_eq!(episodes[0].trajectory.n_transitions(), 2);
+        assert_eq!(episodes[0].trajectory.total_reward(), -0.5);
+        assert!(episodes[1].probabilities.is_none());
+        assert_eq!(episodes[1].trajectory.n_transitions(), 1);
+    }
+
+    #[test]
+    fn test_json_round_trip() { round_trip(Format::Json); }
+
+    #[test]
+    fn test_binary_round_trip() { round_trip(Format::Binary); }
+
+    #[test]
+    fn test_into_batch() {
+        let mut writer = Writer::new(vec![], Format::Json, &Metadata {
+            domain: "test".to_owned(),
+            state_space: String::new(),
+            action_space: String::new(),
+            behaviour: None,
+        })
+        .unwrap();
+
+        writer.write(&episode()).unwrap();
+
+        let bytes = writer.finish().unwrap();
+        let batch = Reader::<_, usize, usize>::new(Cursor::new(bytes))
+            .unwrap()
+            .into_batch()
+            .unwrap();
+
+        assert_eq!(batch.len(), 2);
+        assert!(batch[1].terminated());
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let reader = Reader::<_, usize, usize>::new(Cursor::new(b"{}\n".to_vec()));
+
+        assert!(match reader {
+            Err(Error::InvalidHeader) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_oversized_frame() {
+        let metadata = Metadata::new("Roulette", &Roulette::default());
+        let writer: Writer<_, usize, usize> =
+            Writer::new(vec![], Format::Binary, &metadata).unwrap();
+        let mut bytes = writer.finish().unwrap();
+
+        bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+
+        let mut reader: Reader<_, usize, usize> = Reader::new(Cursor::new(bytes)).unwrap();
+
+        assert!(match reader.next() {
+            Some(Err(Error::Io(ref e))) => e.kind() == io::ErrorKind::InvalidData,
+            _ => false,
+        });
+    }
+}
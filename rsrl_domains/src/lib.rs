@@ -2,8 +2,13 @@
 #[cfg_attr(test, macro_use)]
 extern crate ndarray;
 extern crate rand;
+extern crate rand_distr;
 extern crate spaces;
 
+#[cfg_attr(feature = "serde", macro_use)]
+#[cfg(feature = "serde")]
+extern crate serde_crate;
+
 use crate::spaces::Space;
 use std::iter;
 
@@ -50,6 +55,11 @@ pub type Reward = f64;
 
 /// Container class for data associated with a domain observation.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub enum Observation<S> {
     /// Fully observed state of the environment.
     Full(S),
@@ -127,6 +137,11 @@ impl<S> Observation<S> {
 
 /// Container class for data associated with a domain transition.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct Transition<S, A> {
     /// State transitioned _from_, `s`.
     pub from: Observation<S>,
@@ -331,6 +346,12 @@ impl<'s, 'a, S, A> std::iter::FromIterator<Transition<&'s S, &'a A>> for Vec<Tra
     }
 }
 
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct Trajectory<S, A> {
     pub start: Observation<S>,
     pub steps: Vec<(Observation<S>, A, Reward)>,
@@ -504,6 +525,14 @@ pub use self::cliff_walk::*;
 mod roulette;
 pub use self::roulette::*;
 
+mod river_swim;
+pub use self::river_swim::*;
+
+pub mod bandits;
+
+#[cfg(feature = "dataset")]
+pub mod dataset;
+
 #[cfg(feature = "openai")]
 mod openai;
 #[cfg(feature = "openai")]
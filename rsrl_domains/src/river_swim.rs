@@ -0,0 +1,130 @@
+use crate::{spaces::discrete::Ordinal, Domain, Observation, Reward};
+use rand::{rngs::ThreadRng, thread_rng, Rng};
+
+const LEFT: usize = 0;
+
+const REWARD_LEFT: f64 = 0.005;
+const REWARD_RIGHT: f64 = 1.0;
+
+/// Continuing chain in which an agent swims along a river.
+///
+/// Swimming left, with the current, always succeeds, and yields a small reward
+/// at the leftmost state. Swimming right, against the current, usually fails,
+/// but yields a much larger reward at the rightmost state. Dithering
+/// exploration strategies, such as ε-greedy, rarely reach the right end of the
+/// chain and so settle on the small reward.
+///
+/// # References
+/// - Strehl, A. L., Littman, M. L. (2008). An analysis of model-based interval
+/// estimation for Markov decision processes. Journal of Computer and System
+/// Sciences, 74(8), 1309-1331.
+/// - Osband, I., Russo, D., Van Roy, B. (2013). (More) efficient reinforcement
+/// learning via posterior sampling. In Proceedings of NIPS, pp. 3003-3011.
+#[derive(Debug)]
+pub struct RiverSwim {
+    n_states: usize,
+    state: usize,
+
+    rng: ThreadRng,
+}
+
+impl RiverSwim {
+    pub fn new(n_states: usize) -> Self {
+        if n_states < 2 {
+            panic!("RiverSwim requires at least two states.");
+        }
+
+        RiverSwim {
+            n_states,
+            state: 0,
+
+            rng: thread_rng(),
+        }
+    }
+
+    fn swim_right(&mut self) -> usize {
+        let last = self.n_states - 1;
+        let u: f64 = self.rng.gen();
+
+        match self.state {
+            0 if u < 0.6 => 1,
+            0 => 0,
+            s if s == last && u < 0.4 => s - 1,
+            s if s == last => s,
+            s if u < 0.05 => s - 1,
+            s if u < 0.4 => s + 1,
+            s => s,
+        }
+    }
+}
+
+impl Default for RiverSwim {
+    fn default() -> RiverSwim { RiverSwim::new(6) }
+}
+
+impl Domain for RiverSwim {
+    type StateSpace = Ordinal;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<usize> { Observation::Full(self.state) }
+
+    fn step(&mut self, action: &usize) -> (Observation<usize>, Reward) {
+        let last = self.n_states - 1;
+        let reward = match (self.state, *action) {
+            (0, LEFT) => REWARD_LEFT,
+            (s, a) if s == last && a != LEFT => REWARD_RIGHT,
+            _ => 0.0,
+        };
+
+        self.state = if *action == LEFT {
+            self.state.saturating_sub(1)
+        } else {
+            self.swim_right()
+        };
+
+        (self.emit(), reward)
+    }
+
+    fn state_space(&self) -> Self::StateSpace { Ordinal::new(self.n_states) }
+
+    fn action_space(&self) -> Self::ActionSpace { Ordinal::new(2) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swim_left() {
+        let mut m = RiverSwim::default();
+
+        let (ns, r) = m.step(&0);
+
+        assert_eq!((*ns.state(), r), (0, REWARD_LEFT));
+
+        m.state = 3;
+
+        let (ns, r) = m.step(&0);
+
+        assert_eq!((*ns.state(), r), (2, 0.0));
+        assert!(!m.emit().is_terminal());
+    }
+
+    #[test]
+    fn test_swim_right() {
+        let mut m = RiverSwim::default();
+
+        for _ in 0..1000 {
+            m.step(&1);
+        }
+
+        assert!(m.state > 0);
+
+        m.state = 5;
+
+        let (ns, r) = m.step(&1);
+
+        assert_eq!(r, REWARD_RIGHT);
+        assert!(*ns.state() >= 4);
+    }
+}